@@ -0,0 +1,86 @@
+use chrono::{Duration, Utc};
+use coinpaprika_api::analytics::correlation::{AlignedSeries, FillPolicy, Matrix};
+use coinpaprika_api::analytics::indicators::{self, MacdValue};
+use coinpaprika_api::analytics::risk::RiskReport;
+use coinpaprika_api::analytics::{MissingData, Resample};
+use coinpaprika_api::client::Client;
+use coinpaprika_api::coins::CoinOHLC;
+use coinpaprika_api::tickers::{HistoricalTick, Interval};
+use std::error::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let client = Client::new();
+
+    let end = Utc::now();
+    let historical_ticks: Vec<HistoricalTick> = client
+        .historical_ticks_range("btc-bitcoin", end - Duration::days(7), end)
+        .interval(Interval::Minutes5)
+        .send()
+        .await?;
+
+    //
+    // Resample historical ticks into 4 hour candles.
+    //
+    let candles: Vec<CoinOHLC> = Resample::new(Duration::hours(4))
+        .missing(MissingData::CarryForward)
+        .apply(&historical_ticks)?;
+
+    println!("4h candles: {:#?}", candles);
+
+    //
+    // Compute indicators over daily candles and over raw ticks.
+    //
+    let daily: Vec<CoinOHLC> = client
+        .coin_ohlc_history("btc-bitcoin", end - Duration::days(365), end)
+        .send()
+        .await?
+        .candles;
+
+    let rsi: Vec<Option<f64>> = indicators::rsi(&daily, 14)?;
+    let macd: Vec<Option<MacdValue>> = indicators::macd(&daily, 12, 26, 9)?;
+    let sma: Vec<Option<f64>> = indicators::sma(&historical_ticks, 288)?;
+
+    println!("rsi: {:?}", rsi.last());
+    println!("macd: {:?}", macd.last());
+    println!("24h moving average: {:?}", sma.last());
+
+    //
+    // Get a risk and return report over the last three years of daily prices.
+    //
+    let report: RiskReport = client
+        .coin_risk_report("btc-bitcoin", end - Duration::days(3 * 365), end)
+        .risk_free_rate(0.04)
+        .send()
+        .await?;
+
+    println!("risk report: {:#?}", report);
+
+    //
+    // Correlate daily prices of several coins over the last year.
+    //
+    let series: AlignedSeries = client
+        .aligned_series(
+            vec!["btc-bitcoin", "eth-ethereum", "sol-solana"],
+            end - Duration::days(365),
+            end,
+        )
+        .fill(FillPolicy::Drop)
+        .send()
+        .await?;
+
+    let correlation: Matrix = series.correlation_matrix();
+
+    println!(
+        "btc/eth correlation: {:?}",
+        correlation.get("btc-bitcoin", "eth-ethereum")
+    );
+    println!(
+        "eth beta against btc over the last 90 days: {:?}",
+        series
+            .window(end - Duration::days(90), end)
+            .beta("eth-ethereum", "btc-bitcoin")
+    );
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
+use chrono::{Duration, Utc};
 use coinpaprika_api::changelog::Change;
 use coinpaprika_api::client::Client;
+use futures::TryStreamExt;
 use std::error::Error;
 
 #[tokio::main]
@@ -18,5 +20,17 @@ async fn main() -> Result<(), Box<dyn Error>> {
         changes.iter().take(3).collect::<Vec<&Change>>()
     );
 
+    //
+    // Get all changes made in the last 30 days, fetching as many pages as needed.
+    //
+    let recent_changes: Vec<Change> = client
+        .changelog_all()
+        .since(Utc::now() - Duration::days(30))
+        .stream()
+        .try_collect()
+        .await?;
+
+    println!("changes in the last 30 days: {}", recent_changes.len());
+
     Ok(())
 }
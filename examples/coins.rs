@@ -1,6 +1,7 @@
+use chrono::{Duration, Utc};
 use coinpaprika_api::client::Client;
 use coinpaprika_api::coins::{
-    Coin, CoinDetails, CoinEvent, CoinExchange, CoinMarket, CoinOHLC, Tweet,
+    Coin, CoinDetails, CoinEvent, CoinExchange, CoinMarket, CoinOHLC, OHLCHistory, Tweet,
 };
 use std::error::Error;
 
@@ -24,6 +25,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let coin: CoinDetails = client.coin("btc-bitcoin").send().await?;
 
     println!("btc coin: {:#?}", coin);
+    println!("btc coin symbol: {}", coin.id.symbol());
 
     //
     // Get coin tweets by coin_id.
@@ -100,6 +102,22 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     println!("{:#?}", coin_ohlc_historical);
 
+    //
+    // Get five years of daily open/high/low/close by coin_id, fetched in several requests.
+    //
+    let end = Utc::now();
+    let history: OHLCHistory = client
+        .coin_ohlc_history("btc-bitcoin", end - Duration::days(5 * 365), end)
+        .send()
+        .await?;
+
+    println!(
+        "{} daily candles, {} gaps: {:#?}",
+        history.candles.len(),
+        history.gaps.len(),
+        history.gaps
+    );
+
     //
     // Get coin open/high/low/close for today by coin_id.
     //
@@ -1,5 +1,7 @@
 use coinpaprika_api::client::Client;
 use coinpaprika_api::contracts::Contract;
+use coinpaprika_api::id::PlatformId;
+use coinpaprika_api::tickers::{HistoricalTick, Interval, Ticker};
 use std::error::Error;
 
 #[tokio::main]
@@ -9,11 +11,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
     //
     // Get contract platforms.
     //
-    let contract_platforms: Vec<String> = client.contract_platforms().send().await?;
+    let contract_platforms: Vec<PlatformId> = client.contract_platforms().send().await?;
 
     println!(
         "first 3 contract platforms: {:#?}",
-        contract_platforms.iter().take(3).collect::<Vec<&String>>()
+        contract_platforms
+            .iter()
+            .take(3)
+            .collect::<Vec<&PlatformId>>()
     );
 
     //
@@ -26,5 +31,28 @@ async fn main() -> Result<(), Box<dyn Error>> {
         contracts.iter().take(3).collect::<Vec<&Contract>>()
     );
 
+    //
+    // Get ticker by contract address.
+    //
+    let ticker: Ticker = client
+        .ticker_by_contract("eth-ethereum", "0xdac17f958d2ee523a2206206994597c13d831ec7")
+        .send()
+        .await?;
+
+    println!("usdt ticker: {:#?}", ticker);
+
+    //
+    // Get historical ticks by contract address.
+    //
+    let historical_ticks: Vec<HistoricalTick> = client
+        .historical_ticks_by_contract("eth-ethereum", "0xdac17f958d2ee523a2206206994597c13d831ec7")
+        .start("2022-10-16")
+        .end("2022-10-17")
+        .interval(Interval::Hours1)
+        .send()
+        .await?;
+
+    println!("usdt historical ticks: {:#?}", historical_ticks);
+
     Ok(())
 }
@@ -0,0 +1,91 @@
+use chrono::{Duration, Utc};
+use coinpaprika_api::client::Client;
+use coinpaprika_api::currency::QuoteCurrency;
+use coinpaprika_api::portfolio::{
+    CostBasis, EntryKind, LedgerEntry, LotMethod, Portfolio, Transaction, Valuation, ValuePoint,
+};
+use coinpaprika_api::tickers::Interval;
+use std::error::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let client = Client::new();
+
+    let mut portfolio = Portfolio::new();
+    portfolio
+        .add("btc-bitcoin", 2)
+        .add("eth-ethereum", 15)
+        .add("sol-solana", 120);
+
+    //
+    // Value the portfolio in USD and EUR, with its allocation by tag.
+    //
+    let valuation: Valuation = client
+        .portfolio_valuation(&portfolio)
+        .quotes(vec![QuoteCurrency::Usd, QuoteCurrency::Eur])
+        .tags(true)
+        .send()
+        .await?;
+
+    println!("totals: {:#?}", valuation.totals);
+    println!("positions: {:#?}", valuation.positions);
+    println!("allocation by tag: {:#?}", valuation.tags);
+
+    //
+    // Hourly value over the last two days, with SOL bought a day ago.
+    //
+    let end = Utc::now();
+    let start = end - Duration::days(2);
+
+    let history: Vec<ValuePoint> = client
+        .portfolio_history(&portfolio, start, end)
+        .transactions(vec![Transaction::new(
+            end - Duration::days(1),
+            "sol-solana",
+            30,
+        )])
+        .interval(Interval::Hours1)
+        .send()
+        .await?;
+
+    println!("value history: {:#?}", history);
+
+    //
+    // Realized and unrealized gains of a ledger with HIFO lots, the values of the entries without
+    // one being looked up at their time.
+    //
+    let ledger = vec![
+        LedgerEntry::new(
+            end - Duration::days(300),
+            "eth-ethereum",
+            EntryKind::Buy,
+            10,
+        )
+        .value(25000),
+        LedgerEntry::new(end - Duration::days(200), "eth-ethereum", EntryKind::Buy, 5),
+        LedgerEntry::new(
+            end - Duration::days(100),
+            "eth-ethereum",
+            EntryKind::Sell,
+            8,
+        ),
+        LedgerEntry::new(
+            end - Duration::days(50),
+            "eth-ethereum",
+            EntryKind::TransferOut,
+            2,
+        ),
+    ];
+
+    let cost_basis: CostBasis = client
+        .cost_basis(ledger)
+        .method(LotMethod::Hifo)
+        .send()
+        .await?;
+
+    println!("realized: {:#?}", cost_basis.realized);
+    println!("unrealized: {:#?}", cost_basis.unrealized);
+    println!("gains per year: {:#?}", cost_basis.years);
+
+    Ok(())
+}
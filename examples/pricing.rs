@@ -0,0 +1,60 @@
+use chrono::{Duration, TimeZone, Utc};
+use coinpaprika_api::client::Client;
+use coinpaprika_api::currency::QuoteCurrency;
+use coinpaprika_api::pricing::{ConversionAt, PriceAt, PriceAtMode, RateTable};
+use coinpaprika_api::tickers::ApiPlan;
+use std::error::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let client = Client::new();
+
+    //
+    // Get the USD price of a coin at a past timestamp.
+    //
+    let at = Utc.with_ymd_and_hms(2024, 3, 14, 15, 9, 26).unwrap();
+
+    let price: Option<PriceAt> = client
+        .price_at("btc-bitcoin", at, "usd")
+        .plan(ApiPlan::Free)
+        .mode(PriceAtMode::Interpolated)
+        .send()
+        .await?;
+
+    println!("price at {}: {:#?}", at, price);
+
+    //
+    // Convert 25 ETH into BTC at the same timestamp.
+    //
+    let conversion: Option<ConversionAt> = client
+        .convert_at("eth-ethereum", "btc-bitcoin", 25, at)
+        .plan(ApiPlan::Free)
+        .send()
+        .await?;
+
+    println!("conversion at {}: {:#?}", at, conversion);
+
+    //
+    // Convert between any coins and quote currencies locally from a single tickers snapshot.
+    //
+    let mut rates: RateTable = client
+        .rate_table()
+        .quotes(vec![QuoteCurrency::Eur, QuoteCurrency::Pln])
+        .send()
+        .await?;
+
+    println!(
+        "100 EUR in ETH: {:?}",
+        rates.convert(100, "EUR", "eth-ethereum")
+    );
+    println!(
+        "1 SOL in PLN: {:?}",
+        rates.convert(1, "sol-solana", QuoteCurrency::Pln)
+    );
+
+    if Utc::now() - rates.fetched_at() > Duration::minutes(5) {
+        rates.refresh(&client).await?;
+    }
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
+use chrono::{Duration, Utc};
 use coinpaprika_api::client::Client;
-use coinpaprika_api::tickers::{HistoricalTick, Ticker};
+use coinpaprika_api::currency::QuoteCurrency;
+use coinpaprika_api::decode::LenientList;
+use coinpaprika_api::tickers::{ApiPlan, HistoricalTick, HistoricalTicksPlan, Interval, Ticker};
 use std::error::Error;
 
 #[tokio::main]
@@ -9,10 +12,25 @@ async fn main() -> Result<(), Box<dyn Error>> {
     //
     // Get tickers.
     //
-    let tickers: Vec<Ticker> = client.tickers().quotes(vec!["BTC", "USD"]).send().await?;
+    let tickers: Vec<Ticker> = client
+        .tickers()
+        .quotes(vec![QuoteCurrency::Btc, QuoteCurrency::Usd])
+        .send()
+        .await?;
 
     println!("second ticker: {:#?}", tickers[1]);
 
+    //
+    // Get tickers, skipping the ones that fail to decode.
+    //
+    let tickers: LenientList<Ticker> = client.tickers().send_lenient().await?;
+
+    println!("decoded tickers: {}", tickers.items.len());
+
+    for diagnostic in &tickers.diagnostics {
+        println!("skipped ticker: {}", diagnostic);
+    }
+
     //
     // Get ticker by coin_id.
     //
@@ -31,7 +49,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .historical_ticks("btc-bitcoin")
         .start("2022-10-16")
         .end("2022-10-17")
-        .interval("1d")
+        .interval(Interval::Days1)
         .limit(10)
         .quote("btc")
         .send()
@@ -39,5 +57,39 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     println!("historical ticks: {:#?}", historical_ticks);
 
+    //
+    // Get the last week of historical ticks at the finest interval available in one request.
+    //
+    let end = Utc::now();
+
+    if let Some(plan) = HistoricalTicksPlan::finest(end - Duration::days(7), end, ApiPlan::Free) {
+        let historical_ticks: Vec<HistoricalTick> = client
+            .historical_ticks("btc-bitcoin")
+            .start(&plan.start.timestamp().to_string())
+            .end(&plan.end.timestamp().to_string())
+            .interval(plan.interval)
+            .limit(plan.limit)
+            .send()
+            .await?;
+
+        println!(
+            "{} historical ticks at {} interval",
+            historical_ticks.len(),
+            plan.interval
+        );
+    }
+
+    //
+    // Get two years of hourly historical ticks, fetched in several concurrent requests.
+    //
+    let historical_ticks: Vec<HistoricalTick> = client
+        .historical_ticks_range("btc-bitcoin", end - Duration::days(730), end)
+        .interval(Interval::Hours1)
+        .concurrency(4)
+        .send()
+        .await?;
+
+    println!("{} hourly historical ticks", historical_ticks.len());
+
     Ok(())
 }
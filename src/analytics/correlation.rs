@@ -0,0 +1,265 @@
+use crate::analytics::risk::{log_returns, tick_prices, PriceSeries};
+use crate::client::Client;
+use crate::error::Error;
+use crate::id::CoinId;
+use crate::tickers::{HistoricalTick, Interval};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How timestamps missing from some of the series are handled when aligning them
+pub enum FillPolicy {
+    /// Use the last known price of the series. Timestamps before the first price of any series
+    /// are still dropped.
+    ForwardFill,
+
+    /// Keep only timestamps present in every series
+    Drop,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Prices of several coins on a common timestamp grid
+pub struct AlignedSeries {
+    pub coin_ids: Vec<CoinId>,
+    pub times: Vec<DateTime<Utc>>,
+
+    /// One row per coin, in the order of `coin_ids`, each with one price per element of `times`
+    pub prices: Vec<Vec<f64>>,
+}
+
+impl AlignedSeries {
+    /// Aligns the timestamped prices of each coin, which don't need to be sorted
+    pub fn align(series: Vec<(CoinId, PriceSeries)>, fill: FillPolicy) -> Self {
+        let mut grid: BTreeMap<DateTime<Utc>, Vec<Option<f64>>> = BTreeMap::new();
+
+        for (i, (_, prices)) in series.iter().enumerate() {
+            for (time, price) in prices {
+                grid.entry(*time)
+                    .or_insert_with(|| vec![None; series.len()])[i] = Some(*price);
+            }
+        }
+
+        let mut last: Vec<Option<f64>> = vec![None; series.len()];
+        let mut times = vec![];
+        let mut prices: Vec<Vec<f64>> = vec![vec![]; series.len()];
+
+        for (time, row) in grid {
+            let row: Vec<Option<f64>> = match fill {
+                FillPolicy::ForwardFill => {
+                    for (last, price) in last.iter_mut().zip(&row) {
+                        *last = price.or(*last);
+                    }
+                    last.clone()
+                }
+                FillPolicy::Drop => row,
+            };
+
+            if let Some(row) = row.into_iter().collect::<Option<Vec<f64>>>() {
+                times.push(time);
+                prices
+                    .iter_mut()
+                    .zip(row)
+                    .for_each(|(prices, price)| prices.push(price));
+            }
+        }
+
+        Self {
+            coin_ids: series.into_iter().map(|(coin_id, _)| coin_id).collect(),
+            times,
+            prices,
+        }
+    }
+
+    /// Aligns historical ticks of each coin
+    pub fn from_ticks(ticks: Vec<(CoinId, Vec<HistoricalTick>)>, fill: FillPolicy) -> Self {
+        let series = ticks
+            .into_iter()
+            .map(|(coin_id, ticks)| (coin_id, tick_prices(&ticks)))
+            .collect();
+
+        Self::align(series, fill)
+    }
+
+    /// Aligned prices of `coin_id`
+    pub fn prices(&self, coin_id: impl Into<CoinId>) -> Option<&[f64]> {
+        let index = self.index(&coin_id.into())?;
+        Some(&self.prices[index])
+    }
+
+    /// Part of the series between `start` and `end`, both inclusive
+    pub fn window(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        let from = self.times.partition_point(|time| *time < start);
+        let to = self.times.partition_point(|time| *time <= end).max(from);
+
+        Self {
+            coin_ids: self.coin_ids.clone(),
+            times: self.times[from..to].to_vec(),
+            prices: self
+                .prices
+                .iter()
+                .map(|prices| prices[from..to].to_vec())
+                .collect(),
+        }
+    }
+
+    /// Log returns of each coin, one fewer than `times`
+    pub fn returns(&self) -> Vec<Vec<f64>> {
+        self.prices
+            .iter()
+            .map(|prices| log_returns(prices))
+            .collect()
+    }
+
+    /// Sample covariances of log returns
+    pub fn covariance_matrix(&self) -> Matrix {
+        self.matrix(covariance)
+    }
+
+    /// Pearson correlations of log returns, `None` for coins whose price never changes
+    pub fn correlation_matrix(&self) -> Matrix {
+        self.matrix(|a, b| {
+            let deviation = (covariance(a, a)? * covariance(b, b)?).sqrt();
+
+            (deviation > 0.0).then_some(covariance(a, b)? / deviation)
+        })
+    }
+
+    /// Beta of the log returns of `coin_id` against those of `benchmark`, e.g. `btc-bitcoin`
+    pub fn beta(&self, coin_id: impl Into<CoinId>, benchmark: impl Into<CoinId>) -> Option<f64> {
+        let coin = self.index(&coin_id.into())?;
+        let benchmark = self.index(&benchmark.into())?;
+        let returns = self.returns();
+
+        let variance = covariance(&returns[benchmark], &returns[benchmark])
+            .filter(|variance| *variance > 0.0)?;
+
+        Some(covariance(&returns[coin], &returns[benchmark])? / variance)
+    }
+
+    fn index(&self, coin_id: &CoinId) -> Option<usize> {
+        self.coin_ids.iter().position(|id| id == coin_id)
+    }
+
+    fn matrix(&self, f: impl Fn(&[f64], &[f64]) -> Option<f64>) -> Matrix {
+        let returns = self.returns();
+        let values = returns
+            .iter()
+            .map(|a| returns.iter().map(|b| f(a, b)).collect())
+            .collect();
+
+        Matrix {
+            coin_ids: self.coin_ids.clone(),
+            values,
+        }
+    }
+}
+
+/// Sample covariance of two series of the same length. `None` with fewer than two values.
+fn covariance(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len().min(b.len());
+
+    if n < 2 {
+        return None;
+    }
+
+    let mean_a = a[..n].iter().sum::<f64>() / n as f64;
+    let mean_b = b[..n].iter().sum::<f64>() / n as f64;
+    let sum: f64 = a[..n]
+        .iter()
+        .zip(&b[..n])
+        .map(|(a, b)| (a - mean_a) * (b - mean_b))
+        .sum();
+
+    Some(sum / (n - 1) as f64)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Square matrix of pairwise values between coins
+pub struct Matrix {
+    pub coin_ids: Vec<CoinId>,
+
+    /// `values[i][j]` is the value between `coin_ids[i]` and `coin_ids[j]`, `None` if it could
+    /// not be computed
+    pub values: Vec<Vec<Option<f64>>>,
+}
+
+impl Matrix {
+    /// Value between `a` and `b`
+    pub fn get(&self, a: impl Into<CoinId>, b: impl Into<CoinId>) -> Option<f64> {
+        let (a, b) = (a.into(), b.into());
+        let i = self.coin_ids.iter().position(|id| *id == a)?;
+        let j = self.coin_ids.iter().position(|id| *id == b)?;
+
+        self.values[i][j]
+    }
+}
+
+/// Request for historical ticks of several coins aligned on a common timestamp grid
+pub struct GetAlignedSeriesRequest<'a> {
+    client: &'a Client,
+    coin_ids: Vec<CoinId>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    interval: Interval,
+    quote: String,
+    fill: FillPolicy,
+}
+
+impl<'a> GetAlignedSeriesRequest<'a> {
+    pub fn new<C: Into<CoinId>>(
+        client: &'a Client,
+        coin_ids: Vec<C>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            client,
+            coin_ids: coin_ids.into_iter().map(Into::into).collect(),
+            start,
+            end,
+            interval: Interval::Days1,
+            quote: String::from("usd"),
+            fill: FillPolicy::ForwardFill,
+        }
+    }
+
+    /// Sampling interval of prices
+    ///
+    /// Default: `1d`
+    pub fn interval(&mut self, interval: Interval) -> &'a mut GetAlignedSeriesRequest<'_> {
+        self.interval = interval;
+        self
+    }
+
+    /// Quote of prices (available values: `usd` `btc`)
+    ///
+    /// Default: `"usd"`
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetAlignedSeriesRequest<'_> {
+        self.quote = String::from(quote);
+        self
+    }
+
+    /// Default: [`FillPolicy::ForwardFill`]
+    pub fn fill(&mut self, fill: FillPolicy) -> &'a mut GetAlignedSeriesRequest<'_> {
+        self.fill = fill;
+        self
+    }
+
+    pub async fn send(&self) -> Result<AlignedSeries, Error> {
+        let mut ticks = vec![];
+
+        for coin_id in &self.coin_ids {
+            let coin_ticks = self
+                .client
+                .historical_ticks_range(coin_id, self.start, self.end)
+                .interval(self.interval)
+                .quote(&self.quote)
+                .send()
+                .await?;
+
+            ticks.push((coin_id.clone(), coin_ticks));
+        }
+
+        Ok(AlignedSeries::from_ticks(ticks, self.fill))
+    }
+}
@@ -0,0 +1,575 @@
+use crate::coins::CoinOHLC;
+use crate::error::Error;
+use crate::number::amount_to_f64;
+use crate::tickers::HistoricalTick;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Prices and volume of one element of a series
+pub struct Bar {
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+
+    /// `0` when the element has no volume
+    pub volume: f64,
+}
+
+/// Elements of a series indicators can be computed over
+pub trait ToBar {
+    /// `None` if the element has no price
+    fn to_bar(&self) -> Option<Bar>;
+}
+
+impl ToBar for Bar {
+    fn to_bar(&self) -> Option<Bar> {
+        Some(*self)
+    }
+}
+
+impl ToBar for CoinOHLC {
+    fn to_bar(&self) -> Option<Bar> {
+        let close = amount_to_f64(self.close?);
+
+        Some(Bar {
+            high: self.high.map(amount_to_f64).unwrap_or(close),
+            low: self.low.map(amount_to_f64).unwrap_or(close),
+            close,
+            volume: self.volume.map(amount_to_f64).unwrap_or(0.0),
+        })
+    }
+}
+
+/// A tick is a bar whose high, low and close are all the tick price. Its volume is the rolling
+/// `volume_24h`, so volume based indicators over ticks are only indicative.
+impl ToBar for HistoricalTick {
+    fn to_bar(&self) -> Option<Bar> {
+        let price = amount_to_f64(self.price);
+
+        Some(Bar {
+            high: price,
+            low: price,
+            close: price,
+            volume: self.volume_24h.map(amount_to_f64).unwrap_or(0.0),
+        })
+    }
+}
+
+fn check_period(field: &'static str, period: usize) -> Result<(), Error> {
+    if period == 0 {
+        return Err(Error::Validation {
+            field,
+            reason: String::from("must be at least 1, got 0"),
+        });
+    }
+
+    Ok(())
+}
+
+/// Feeds every bar of `series` to `update`, keeping `None` for elements without a price.
+fn over<T: ToBar, O>(series: &[T], mut update: impl FnMut(Bar) -> Option<O>) -> Vec<Option<O>> {
+    series
+        .iter()
+        .map(|element| element.to_bar().and_then(&mut update))
+        .collect()
+}
+
+//
+// Moving averages
+//
+
+#[derive(Debug, Clone)]
+/// Simple moving average
+pub struct Sma {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl Sma {
+    pub fn new(period: usize) -> Result<Self, Error> {
+        check_period("period", period)?;
+
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+            sum: 0.0,
+        })
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.window.push_back(value);
+        self.sum += value;
+
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap_or_default();
+        }
+
+        (self.window.len() == self.period).then(|| self.sum / self.period as f64)
+    }
+}
+
+/// Simple moving average of closes
+pub fn sma<T: ToBar>(series: &[T], period: usize) -> Result<Vec<Option<f64>>, Error> {
+    let mut sma = Sma::new(period)?;
+    Ok(over(series, |bar| sma.update(bar.close)))
+}
+
+#[derive(Debug, Clone)]
+/// Exponential moving average, seeded with the simple moving average of the first `period`
+/// values
+pub struct Ema {
+    alpha: f64,
+    seed: Sma,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(period: usize) -> Result<Self, Error> {
+        Ok(Self {
+            alpha: 2.0 / (period as f64 + 1.0),
+            seed: Sma::new(period)?,
+            value: None,
+        })
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.value = match self.value {
+            Some(previous) => Some(previous + self.alpha * (value - previous)),
+            None => self.seed.update(value),
+        };
+
+        self.value
+    }
+}
+
+/// Exponential moving average of closes
+pub fn ema<T: ToBar>(series: &[T], period: usize) -> Result<Vec<Option<f64>>, Error> {
+    let mut ema = Ema::new(period)?;
+    Ok(over(series, |bar| ema.update(bar.close)))
+}
+
+#[derive(Debug, Clone)]
+/// Linearly weighted moving average, the latest value having the weight `period`
+pub struct Wma {
+    period: usize,
+    window: VecDeque<f64>,
+}
+
+impl Wma {
+    pub fn new(period: usize) -> Result<Self, Error> {
+        check_period("period", period)?;
+
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+        })
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.window.push_back(value);
+
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let weighted: f64 = self
+            .window
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (i + 1) as f64 * value)
+            .sum();
+        let weights = (self.period * (self.period + 1) / 2) as f64;
+
+        Some(weighted / weights)
+    }
+}
+
+/// Linearly weighted moving average of closes
+pub fn wma<T: ToBar>(series: &[T], period: usize) -> Result<Vec<Option<f64>>, Error> {
+    let mut wma = Wma::new(period)?;
+    Ok(over(series, |bar| wma.update(bar.close)))
+}
+
+//
+// Oscillators
+//
+
+#[derive(Debug, Clone)]
+/// Relative strength index with Wilder's smoothing, between `0` and `100`
+pub struct Rsi {
+    period: usize,
+    previous: Option<f64>,
+    changes: usize,
+    gain: f64,
+    loss: f64,
+}
+
+impl Rsi {
+    pub fn new(period: usize) -> Result<Self, Error> {
+        check_period("period", period)?;
+
+        Ok(Self {
+            period,
+            previous: None,
+            changes: 0,
+            gain: 0.0,
+            loss: 0.0,
+        })
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        let previous = self.previous.replace(value)?;
+        let (gain, loss) = match value - previous {
+            change if change > 0.0 => (change, 0.0),
+            change => (0.0, -change),
+        };
+        let period = self.period as f64;
+
+        self.changes += 1;
+
+        if self.changes <= self.period {
+            self.gain += gain / period;
+            self.loss += loss / period;
+        } else {
+            self.gain = (self.gain * (period - 1.0) + gain) / period;
+            self.loss = (self.loss * (period - 1.0) + loss) / period;
+        }
+
+        if self.changes < self.period {
+            return None;
+        }
+
+        if self.loss == 0.0 {
+            return Some(if self.gain == 0.0 { 50.0 } else { 100.0 });
+        }
+
+        Some(100.0 - 100.0 / (1.0 + self.gain / self.loss))
+    }
+}
+
+/// Relative strength index of closes
+pub fn rsi<T: ToBar>(series: &[T], period: usize) -> Result<Vec<Option<f64>>, Error> {
+    let mut rsi = Rsi::new(period)?;
+    Ok(over(series, |bar| rsi.update(bar.close)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Moving average convergence/divergence values
+pub struct MacdValue {
+    /// Fast EMA minus slow EMA
+    pub macd: f64,
+
+    /// EMA of `macd`, `None` until enough `macd` values are known
+    pub signal: Option<f64>,
+
+    /// `macd` minus `signal`
+    pub histogram: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+/// Moving average convergence/divergence, commonly with periods 12, 26 and 9
+pub struct Macd {
+    fast: Ema,
+    slow: Ema,
+    signal: Ema,
+}
+
+impl Macd {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Result<Self, Error> {
+        check_period("fast", fast)?;
+        check_period("slow", slow)?;
+        check_period("signal", signal)?;
+
+        Ok(Self {
+            fast: Ema::new(fast)?,
+            slow: Ema::new(slow)?,
+            signal: Ema::new(signal)?,
+        })
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<MacdValue> {
+        let (fast, slow) = (self.fast.update(value), self.slow.update(value));
+        let macd = fast? - slow?;
+        let signal = self.signal.update(macd);
+
+        Some(MacdValue {
+            macd,
+            signal,
+            histogram: signal.map(|signal| macd - signal),
+        })
+    }
+}
+
+/// Moving average convergence/divergence of closes
+pub fn macd<T: ToBar>(
+    series: &[T],
+    fast: usize,
+    slow: usize,
+    signal: usize,
+) -> Result<Vec<Option<MacdValue>>, Error> {
+    let mut macd = Macd::new(fast, slow, signal)?;
+    Ok(over(series, |bar| macd.update(bar.close)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Stochastic oscillator values, between `0` and `100`
+pub struct StochasticValue {
+    /// Position of the close in the high/low range of the last `k` bars
+    pub k: f64,
+
+    /// Simple moving average of `k`, `None` until enough `k` values are known
+    pub d: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+/// Stochastic oscillator, commonly with periods 14 and 3
+pub struct Stochastic {
+    period: usize,
+    window: VecDeque<Bar>,
+    d: Sma,
+}
+
+impl Stochastic {
+    pub fn new(k: usize, d: usize) -> Result<Self, Error> {
+        check_period("k", k)?;
+        check_period("d", d)?;
+
+        Ok(Self {
+            period: k,
+            window: VecDeque::with_capacity(k + 1),
+            d: Sma::new(d)?,
+        })
+    }
+
+    pub fn update(&mut self, bar: Bar) -> Option<StochasticValue> {
+        self.window.push_back(bar);
+
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let high = self
+            .window
+            .iter()
+            .map(|bar| bar.high)
+            .fold(f64::MIN, f64::max);
+        let low = self
+            .window
+            .iter()
+            .map(|bar| bar.low)
+            .fold(f64::MAX, f64::min);
+        let k = match high > low {
+            true => 100.0 * (bar.close - low) / (high - low),
+            false => 50.0,
+        };
+
+        Some(StochasticValue {
+            k,
+            d: self.d.update(k),
+        })
+    }
+}
+
+/// Stochastic oscillator over bars
+pub fn stochastic<T: ToBar>(
+    series: &[T],
+    k: usize,
+    d: usize,
+) -> Result<Vec<Option<StochasticValue>>, Error> {
+    let mut stochastic = Stochastic::new(k, d)?;
+    Ok(over(series, |bar| stochastic.update(bar)))
+}
+
+//
+// Volatility
+//
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Bollinger Bands values
+pub struct BollingerBands {
+    pub lower: f64,
+
+    /// Simple moving average
+    pub middle: f64,
+
+    pub upper: f64,
+}
+
+#[derive(Debug, Clone)]
+/// Bollinger Bands: simple moving average plus and minus `k` population standard deviations,
+/// commonly with period 20 and `k` 2
+pub struct Bollinger {
+    period: usize,
+    k: f64,
+    window: VecDeque<f64>,
+}
+
+impl Bollinger {
+    pub fn new(period: usize, k: f64) -> Result<Self, Error> {
+        check_period("period", period)?;
+
+        Ok(Self {
+            period,
+            k,
+            window: VecDeque::with_capacity(period + 1),
+        })
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<BollingerBands> {
+        self.window.push_back(value);
+
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let n = self.period as f64;
+        let mean = self.window.iter().sum::<f64>() / n;
+        let variance = self.window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        let width = self.k * variance.sqrt();
+
+        Some(BollingerBands {
+            lower: mean - width,
+            middle: mean,
+            upper: mean + width,
+        })
+    }
+}
+
+/// Bollinger Bands of closes
+pub fn bollinger<T: ToBar>(
+    series: &[T],
+    period: usize,
+    k: f64,
+) -> Result<Vec<Option<BollingerBands>>, Error> {
+    let mut bollinger = Bollinger::new(period, k)?;
+    Ok(over(series, |bar| bollinger.update(bar.close)))
+}
+
+#[derive(Debug, Clone)]
+/// Average true range with Wilder's smoothing
+pub struct Atr {
+    period: usize,
+    previous: Option<f64>,
+    ranges: usize,
+    value: f64,
+}
+
+impl Atr {
+    pub fn new(period: usize) -> Result<Self, Error> {
+        check_period("period", period)?;
+
+        Ok(Self {
+            period,
+            previous: None,
+            ranges: 0,
+            value: 0.0,
+        })
+    }
+
+    pub fn update(&mut self, bar: Bar) -> Option<f64> {
+        let range = match self.previous {
+            Some(close) => (bar.high - bar.low)
+                .max((bar.high - close).abs())
+                .max((bar.low - close).abs()),
+            None => bar.high - bar.low,
+        };
+        let period = self.period as f64;
+
+        self.previous = Some(bar.close);
+        self.ranges += 1;
+
+        self.value = match self.ranges <= self.period {
+            true => self.value + range / period,
+            false => (self.value * (period - 1.0) + range) / period,
+        };
+
+        (self.ranges >= self.period).then_some(self.value)
+    }
+}
+
+/// Average true range over bars
+pub fn atr<T: ToBar>(series: &[T], period: usize) -> Result<Vec<Option<f64>>, Error> {
+    let mut atr = Atr::new(period)?;
+    Ok(over(series, |bar| atr.update(bar)))
+}
+
+//
+// Volume
+//
+
+#[derive(Debug, Clone, Default)]
+/// Volume weighted average price of the typical price `(high + low + close) / 3`, accumulated
+/// since creation or the last [`reset`](Vwap::reset)
+pub struct Vwap {
+    weighted: f64,
+    volume: f64,
+}
+
+impl Vwap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new session
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// `None` while the accumulated volume is zero
+    pub fn update(&mut self, bar: Bar) -> Option<f64> {
+        self.weighted += (bar.high + bar.low + bar.close) / 3.0 * bar.volume;
+        self.volume += bar.volume;
+
+        (self.volume > 0.0).then(|| self.weighted / self.volume)
+    }
+}
+
+/// Volume weighted average price over the whole series
+pub fn vwap<T: ToBar>(series: &[T]) -> Vec<Option<f64>> {
+    let mut vwap = Vwap::new();
+    over(series, |bar| vwap.update(bar))
+}
+
+#[derive(Debug, Clone, Default)]
+/// On-balance volume, starting at `0`
+pub struct Obv {
+    previous: Option<f64>,
+    value: f64,
+}
+
+impl Obv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, bar: Bar) -> f64 {
+        if let Some(previous) = self.previous.replace(bar.close) {
+            if bar.close > previous {
+                self.value += bar.volume;
+            } else if bar.close < previous {
+                self.value -= bar.volume;
+            }
+        }
+
+        self.value
+    }
+}
+
+/// On-balance volume over bars
+pub fn obv<T: ToBar>(series: &[T]) -> Vec<Option<f64>> {
+    let mut obv = Obv::new();
+    over(series, |bar| Some(obv.update(bar)))
+}
@@ -0,0 +1,13 @@
+mod resample;
+pub use resample::{MissingData, Resample};
+
+/// Technical indicators, each available as a streaming struct fed with `update` and as a function
+/// over a whole series. Series functions return one entry per element, `None` until the indicator
+/// has seen enough values and for elements without a price.
+pub mod indicators;
+
+/// Returns, volatility, drawdowns and risk adjusted return ratios of price histories
+pub mod risk;
+
+/// Multi-coin price series aligned on a common timestamp grid, with their correlations
+pub mod correlation;
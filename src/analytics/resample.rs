@@ -0,0 +1,161 @@
+use crate::coins::CoinOHLC;
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
+use crate::error::Error;
+use crate::number::{amount_from_f64, amount_to_f64, Amount, Price};
+use crate::tickers::HistoricalTick;
+use chrono::{DateTime, Duration, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How candles without any tick are handled
+pub enum MissingData {
+    /// Leave the candle out
+    Skip,
+
+    /// Emit a flat candle at the previous close, with zero volume and the previous market
+    /// capitalization
+    CarryForward,
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Resampling of historical ticks into candles of a fixed timeframe
+pub struct Resample {
+    timeframe: Duration,
+    anchor: DateTime<Utc>,
+    missing: MissingData,
+}
+
+impl Resample {
+    pub fn new(timeframe: Duration) -> Self {
+        Self {
+            timeframe,
+            anchor: DateTime::UNIX_EPOCH,
+            missing: MissingData::Skip,
+        }
+    }
+
+    /// Start of any candle. Candles open at `anchor` plus a multiple of the timeframe, e.g.
+    /// a Monday midnight for weekly candles starting on Mondays.
+    ///
+    /// Default: `1970-01-01T00:00:00Z` (a Thursday)
+    pub fn anchor(&mut self, anchor: DateTime<Utc>) -> &mut Resample {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Default: [`MissingData::Skip`]
+    pub fn missing(&mut self, missing: MissingData) -> &mut Resample {
+        self.missing = missing;
+        self
+    }
+
+    /// Builds candles from `ticks`, which don't need to be sorted. Ticks with an unparsable
+    /// `timestamp` are ignored.
+    ///
+    /// `open`, `high`, `low` and `close` come from tick prices and `market_cap` from the last
+    /// tick. Ticks only carry a rolling 24h volume, so `volume` is estimated as the mean
+    /// `volume_24h` of the candle scaled down to the timeframe.
+    pub fn apply(&self, ticks: &[HistoricalTick]) -> Result<Vec<CoinOHLC>, Error> {
+        let step = self.timeframe.num_seconds();
+
+        if step < 1 {
+            return Err(Error::Validation {
+                field: "timeframe",
+                reason: format!("must be at least 1 second, got {}", self.timeframe),
+            });
+        }
+
+        let mut ticks: Vec<(DateTime<Utc>, &HistoricalTick)> = ticks
+            .iter()
+            .filter_map(|tick| tick.datetime().map(|time| (time, tick)))
+            .collect();
+        ticks.sort_by_key(|(time, _)| *time);
+
+        let ticks: Vec<(i64, &HistoricalTick)> = ticks
+            .into_iter()
+            .map(|(time, tick)| ((time - self.anchor).num_seconds().div_euclid(step), tick))
+            .collect();
+
+        let mut candles: Vec<CoinOHLC> = vec![];
+        let mut previous: Option<i64> = None;
+
+        for group in ticks.chunk_by(|a, b| a.0 == b.0) {
+            let bucket = group[0].0;
+
+            if let (Some(previous), MissingData::CarryForward) = (previous, self.missing) {
+                for missing in previous + 1..bucket {
+                    let flat = candles.last().map(|last| self.flat(missing, last));
+                    candles.extend(flat);
+                }
+            }
+
+            let ticks: Vec<&HistoricalTick> = group.iter().map(|(_, tick)| *tick).collect();
+            candles.push(self.candle(bucket, &ticks));
+            previous = Some(bucket);
+        }
+
+        Ok(candles)
+    }
+
+    fn candle(&self, bucket: i64, ticks: &[&HistoricalTick]) -> CoinOHLC {
+        let prices = ticks.iter().map(|tick| tick.price);
+        let high = prices.clone().reduce(|a, b| if b > a { b } else { a });
+        let low = prices.reduce(|a, b| if b < a { b } else { a });
+
+        let volumes: Vec<f64> = ticks
+            .iter()
+            .filter_map(|tick| tick.volume_24h.map(amount_to_f64))
+            .collect();
+        let scale = self.timeframe.num_seconds() as f64 / 86_400.0;
+        let volume = match volumes.is_empty() {
+            true => None,
+            false => amount_from_f64(volumes.iter().sum::<f64>() / volumes.len() as f64 * scale),
+        };
+
+        let (time_open, time_close) = self.bounds(bucket);
+
+        CoinOHLC {
+            time_open,
+            time_close,
+            open: ticks.first().map(|tick| tick.price),
+            high,
+            low,
+            close: ticks.last().map(|tick| tick.price),
+            volume,
+            market_cap: ticks.iter().rev().find_map(|tick| tick.market_cap),
+            #[cfg(feature = "extra-fields")]
+            extra: Extra::new(),
+        }
+    }
+
+    fn flat(&self, bucket: i64, last: &CoinOHLC) -> CoinOHLC {
+        let close: Option<Price> = last.close;
+        let (time_open, time_close) = self.bounds(bucket);
+
+        CoinOHLC {
+            time_open,
+            time_close,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: Some(Amount::default()),
+            market_cap: last.market_cap,
+            #[cfg(feature = "extra-fields")]
+            extra: Extra::new(),
+        }
+    }
+
+    /// `time_open` and `time_close` of a candle, formatted like the API does
+    fn bounds(&self, bucket: i64) -> (String, String) {
+        let step = self.timeframe.num_seconds();
+        let open = self.anchor + Duration::seconds(step * bucket);
+        let close = open + Duration::seconds(step - 1);
+
+        (format_time(open), format_time(close))
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
@@ -0,0 +1,346 @@
+use crate::client::Client;
+use crate::coins::CoinOHLC;
+use crate::error::Error;
+use crate::id::CoinId;
+use crate::number::amount_to_f64;
+use crate::tickers::{HistoricalTick, Interval};
+use chrono::{DateTime, Duration, Utc};
+
+/// Timestamped prices
+pub type PriceSeries = Vec<(DateTime<Utc>, f64)>;
+
+/// Number of periods of length `period` in a year, markets trading around the clock
+pub fn periods_per_year(period: Duration) -> f64 {
+    Duration::days(365).num_seconds() as f64 / period.num_seconds() as f64
+}
+
+/// Timestamps and prices of ticks, skipping ticks with an unparsable `timestamp`
+pub fn tick_prices(ticks: &[HistoricalTick]) -> PriceSeries {
+    ticks
+        .iter()
+        .filter_map(|tick| Some((tick.datetime()?, amount_to_f64(tick.price))))
+        .collect()
+}
+
+/// Opening times and closes of candles, skipping candles without a close or with an unparsable
+/// `time_open`
+pub fn candle_prices(candles: &[CoinOHLC]) -> PriceSeries {
+    candles
+        .iter()
+        .filter_map(|candle| Some((candle.open_datetime()?, amount_to_f64(candle.close?))))
+        .collect()
+}
+
+/// `prices[i] / prices[i - 1] - 1` for every consecutive pair of prices
+pub fn simple_returns(prices: &[f64]) -> Vec<f64> {
+    prices
+        .windows(2)
+        .map(|pair| pair[1] / pair[0] - 1.0)
+        .collect()
+}
+
+/// `ln(prices[i] / prices[i - 1])` for every consecutive pair of prices
+pub fn log_returns(prices: &[f64]) -> Vec<f64> {
+    prices
+        .windows(2)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect()
+}
+
+/// Annualized sample standard deviation of `returns`. `None` with fewer than two returns.
+pub fn volatility(returns: &[f64], periods_per_year: f64) -> Option<f64> {
+    Some(std_dev(returns)? * periods_per_year.sqrt())
+}
+
+/// Annualized Sharpe ratio of `returns` for an annual `risk_free_rate` (e.g. `0.04`). `None`
+/// with fewer than two returns or without any variation.
+pub fn sharpe(returns: &[f64], risk_free_rate: f64, periods_per_year: f64) -> Option<f64> {
+    let excess = mean(returns)? - risk_free_rate / periods_per_year;
+    let deviation = std_dev(returns).filter(|deviation| *deviation > 0.0)?;
+
+    Some(excess / deviation * periods_per_year.sqrt())
+}
+
+/// Annualized Sortino ratio of `returns` for an annual `risk_free_rate`, penalizing only
+/// returns below it. `None` without any return below the risk free rate.
+pub fn sortino(returns: &[f64], risk_free_rate: f64, periods_per_year: f64) -> Option<f64> {
+    let target = risk_free_rate / periods_per_year;
+    let excess = mean(returns)? - target;
+    let downside = returns
+        .iter()
+        .map(|r| (r - target).min(0.0).powi(2))
+        .sum::<f64>()
+        / returns.len() as f64;
+
+    (downside > 0.0).then(|| excess / downside.sqrt() * periods_per_year.sqrt())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Decline from a peak price, as indexes into the price series
+pub struct Drawdown {
+    pub peak: usize,
+
+    /// Lowest price before recovery
+    pub trough: usize,
+
+    /// First price back at or above the peak, `None` if not recovered yet
+    pub recovery: Option<usize>,
+
+    /// Fraction lost from peak to trough, e.g. `0.25` for a 25% decline
+    pub depth: f64,
+}
+
+/// All drawdowns of `prices`, in order
+pub fn drawdowns(prices: &[f64]) -> Vec<Drawdown> {
+    let mut drawdowns = vec![];
+    let mut current: Option<Drawdown> = None;
+    let mut peak = 0;
+
+    for (i, &price) in prices.iter().enumerate() {
+        if price >= prices[peak] {
+            if let Some(mut drawdown) = current.take() {
+                drawdown.recovery = Some(i);
+                drawdowns.push(drawdown);
+            }
+            peak = i;
+            continue;
+        }
+
+        // A peak at zero or below has nothing to lose.
+        let depth = match prices[peak] > 0.0 {
+            true => 1.0 - price / prices[peak],
+            false => 0.0,
+        };
+        let drawdown = current.get_or_insert(Drawdown {
+            peak,
+            trough: i,
+            recovery: None,
+            depth,
+        });
+
+        if depth > drawdown.depth {
+            drawdown.trough = i;
+            drawdown.depth = depth;
+        }
+    }
+
+    drawdowns.extend(current);
+    drawdowns
+}
+
+/// Deepest drawdown of `prices`
+pub fn max_drawdown(prices: &[f64]) -> Option<Drawdown> {
+    drawdowns(prices)
+        .into_iter()
+        .reduce(|a, b| if b.depth > a.depth { b } else { a })
+}
+
+/// Applies `f` to every window of `window` values ending at each index of `values`
+fn rolling(values: &[f64], window: usize, f: impl Fn(&[f64]) -> Option<f64>) -> Vec<Option<f64>> {
+    (0..values.len())
+        .map(|end| match window > 0 && end + 1 >= window {
+            true => f(&values[end + 1 - window..=end]),
+            false => None,
+        })
+        .collect()
+}
+
+/// [`volatility`] of every window of `window` returns, aligned with `returns`
+pub fn rolling_volatility(
+    returns: &[f64],
+    window: usize,
+    periods_per_year: f64,
+) -> Vec<Option<f64>> {
+    rolling(returns, window, |returns| {
+        volatility(returns, periods_per_year)
+    })
+}
+
+/// [`sharpe`] of every window of `window` returns, aligned with `returns`
+pub fn rolling_sharpe(
+    returns: &[f64],
+    window: usize,
+    risk_free_rate: f64,
+    periods_per_year: f64,
+) -> Vec<Option<f64>> {
+    rolling(returns, window, |returns| {
+        sharpe(returns, risk_free_rate, periods_per_year)
+    })
+}
+
+/// [`sortino`] of every window of `window` returns, aligned with `returns`
+pub fn rolling_sortino(
+    returns: &[f64],
+    window: usize,
+    risk_free_rate: f64,
+    periods_per_year: f64,
+) -> Vec<Option<f64>> {
+    rolling(returns, window, |returns| {
+        sortino(returns, risk_free_rate, periods_per_year)
+    })
+}
+
+/// Depth of the [`max_drawdown`] of every window of `window` prices, aligned with `prices`
+pub fn rolling_max_drawdown(prices: &[f64], window: usize) -> Vec<Option<f64>> {
+    rolling(prices, window, |prices| {
+        Some(max_drawdown(prices).map_or(0.0, |drawdown| drawdown.depth))
+    })
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+fn std_dev(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let mean = mean(values)?;
+    let variance =
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+
+    Some(variance.sqrt())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Drawdown with the times of its peak, trough and recovery
+pub struct DrawdownPeriod {
+    pub peak: DateTime<Utc>,
+    pub trough: DateTime<Utc>,
+    pub recovery: Option<DateTime<Utc>>,
+
+    /// Fraction lost from peak to trough, e.g. `0.25` for a 25% decline
+    pub depth: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Risk and return summary of a price history
+pub struct RiskReport {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub points: usize,
+
+    /// Simple return from the first to the last price
+    pub total_return: Option<f64>,
+
+    /// Annualized volatility of log returns
+    pub volatility: Option<f64>,
+
+    /// Annualized Sharpe ratio of simple returns
+    pub sharpe: Option<f64>,
+
+    /// Annualized Sortino ratio of simple returns
+    pub sortino: Option<f64>,
+
+    pub max_drawdown: Option<DrawdownPeriod>,
+    pub drawdowns: Vec<DrawdownPeriod>,
+}
+
+impl RiskReport {
+    /// Report over `prices`, sorted by time and sampled every `1 / periods_per_year` of a year.
+    /// `risk_free_rate` is annual, e.g. `0.04`.
+    pub fn new(
+        prices: &[(DateTime<Utc>, f64)],
+        periods_per_year: f64,
+        risk_free_rate: f64,
+    ) -> Self {
+        let times: Vec<DateTime<Utc>> = prices.iter().map(|(time, _)| *time).collect();
+        let values: Vec<f64> = prices.iter().map(|(_, price)| *price).collect();
+        let returns = simple_returns(&values);
+
+        let period = |drawdown: Drawdown| DrawdownPeriod {
+            peak: times[drawdown.peak],
+            trough: times[drawdown.trough],
+            recovery: drawdown.recovery.map(|recovery| times[recovery]),
+            depth: drawdown.depth,
+        };
+
+        Self {
+            start: times.first().copied(),
+            end: times.last().copied(),
+            points: prices.len(),
+            total_return: match (values.first(), values.last()) {
+                (Some(first), Some(last)) if values.len() > 1 && *first != 0.0 => {
+                    Some(last / first - 1.0)
+                }
+                _ => None,
+            },
+            volatility: volatility(&log_returns(&values), periods_per_year),
+            sharpe: sharpe(&returns, risk_free_rate, periods_per_year),
+            sortino: sortino(&returns, risk_free_rate, periods_per_year),
+            max_drawdown: max_drawdown(&values).map(period),
+            drawdowns: drawdowns(&values).into_iter().map(period).collect(),
+        }
+    }
+}
+
+/// Request for a [`RiskReport`] of a coin, computed from its historical ticks
+pub struct GetCoinRiskReportRequest<'a> {
+    client: &'a Client,
+    coin_id: CoinId,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    interval: Interval,
+    quote: String,
+    risk_free_rate: f64,
+}
+
+impl<'a> GetCoinRiskReportRequest<'a> {
+    pub fn new(
+        client: &'a Client,
+        coin_id: impl Into<CoinId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            client,
+            coin_id: coin_id.into(),
+            start,
+            end,
+            interval: Interval::Days1,
+            quote: String::from("usd"),
+            risk_free_rate: 0.0,
+        }
+    }
+
+    /// Sampling interval of prices
+    ///
+    /// Default: `1d`
+    pub fn interval(&mut self, interval: Interval) -> &'a mut GetCoinRiskReportRequest<'_> {
+        self.interval = interval;
+        self
+    }
+
+    /// Quote of prices (available values: `usd` `btc`)
+    ///
+    /// Default: `"usd"`
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetCoinRiskReportRequest<'_> {
+        self.quote = String::from(quote);
+        self
+    }
+
+    /// Annual risk free rate used by Sharpe and Sortino ratios, e.g. `0.04`
+    ///
+    /// Default: `0`
+    pub fn risk_free_rate(&mut self, risk_free_rate: f64) -> &'a mut GetCoinRiskReportRequest<'_> {
+        self.risk_free_rate = risk_free_rate;
+        self
+    }
+
+    pub async fn send(&self) -> Result<RiskReport, Error> {
+        let ticks = self
+            .client
+            .historical_ticks_range(&self.coin_id, self.start, self.end)
+            .interval(self.interval)
+            .quote(&self.quote)
+            .send()
+            .await?;
+
+        Ok(RiskReport::new(
+            &tick_prices(&ticks),
+            periods_per_year(self.interval.duration()),
+            self.risk_free_rate,
+        ))
+    }
+}
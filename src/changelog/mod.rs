@@ -1,17 +1,27 @@
 use crate::client::{Client, Response};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
 use crate::error::Error;
+use crate::id::CoinId;
+use crate::validate;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, TryStreamExt};
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Change made by coinpaprika.com moderators
 pub struct Change {
-    pub currency_id: String,
-    pub old_id: String,
-    pub new_id: String,
+    pub currency_id: CoinId,
+    pub old_id: CoinId,
+    pub new_id: CoinId,
 
     /// RFC3999 (ISO-8601) format
     pub changed_at: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Request for getting coin id changes made by coinpaprika.com moderators
@@ -27,6 +37,8 @@ impl<'a> GetChangelogRequest<'a> {
     }
 
     pub async fn send(&self) -> Result<Vec<Change>, Error> {
+        validate::at_least("page", self.page, 1)?;
+
         let query: Vec<(String, String)> = vec![("page".to_string(), self.page.to_string())];
 
         let request: RequestBuilder = self
@@ -37,8 +49,76 @@ impl<'a> GetChangelogRequest<'a> {
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<Change> = response.response.json().await?;
+        let data: Vec<Change> = response.json().await?;
 
         Ok(data)
     }
 }
+
+/// Request for getting all coin id changes made by coinpaprika.com moderators, walking
+/// [/changelog/ids](https://api.coinpaprika.com/#tag/Changelog/operation/getChangelogIDs) pages
+/// until an empty page
+pub struct GetChangelogStreamRequest<'a> {
+    client: &'a Client,
+    since: Option<DateTime<Utc>>,
+}
+
+impl<'a> GetChangelogStreamRequest<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            since: None,
+        }
+    }
+
+    /// Only return changes made at or after `since`. Pages are returned newest first, so paging
+    /// stops at the first page containing an older change.
+    pub fn since(&mut self, since: DateTime<Utc>) -> &'a mut GetChangelogStreamRequest<'_> {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn stream(&self) -> impl Stream<Item = Result<Change, Error>> + 'a {
+        let client = self.client;
+        let since = self.since;
+
+        stream::try_unfold(Some(1), move |page| async move {
+            let page = match page {
+                Some(page) => page,
+                None => return Ok::<_, Error>(None),
+            };
+
+            let changes = GetChangelogRequest::new(client, page).send().await?;
+
+            if changes.is_empty() {
+                return Ok(None);
+            }
+
+            let (changes, next) = match since {
+                Some(since) => {
+                    let count = changes.len();
+                    let changes: Vec<Change> = changes
+                        .into_iter()
+                        .filter(|change| !is_before(change, since))
+                        .collect();
+
+                    let next = (changes.len() == count).then_some(page + 1);
+
+                    (changes, next)
+                }
+                None => (changes, Some(page + 1)),
+            };
+
+            Ok(Some((changes, next)))
+        })
+        .map_ok(|changes| stream::iter(changes.into_iter().map(Ok)))
+        .try_flatten()
+    }
+}
+
+/// Whether `change` was made before `since`. Changes with unparsable dates are never skipped.
+fn is_before(change: &Change, since: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(&change.changed_at)
+        .map(|changed_at| changed_at < since)
+        .unwrap_or(false)
+}
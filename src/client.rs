@@ -1,23 +1,40 @@
-use crate::changelog::GetChangelogRequest;
+use crate::analytics::correlation::GetAlignedSeriesRequest;
+use crate::analytics::risk::GetCoinRiskReportRequest;
+use crate::changelog::{GetChangelogRequest, GetChangelogStreamRequest};
 use crate::coins::{
     GetCoinEventsRequest, GetCoinExchangesRequest, GetCoinMarketsRequest,
-    GetCoinOHLCHistoricalRequest, GetCoinOHLCLastFullDayRequest, GetCoinOHLCTodayRequest,
-    GetCoinRequest, GetCoinsRequest, GetTwitterRequest,
+    GetCoinOHLCHistoricalRequest, GetCoinOHLCHistoryRequest, GetCoinOHLCLastFullDayRequest,
+    GetCoinOHLCTodayRequest, GetCoinRequest, GetCoinsRequest, GetTwitterRequest,
 };
-use crate::contracts::{GetContractPlatformsRequest, GetContractsRequest};
+use crate::contracts::{
+    GetContractPlatformsRequest, GetContractsRequest, GetHistoricalTicksByContractRequest,
+    GetTickerByContractRequest,
+};
+use crate::decode;
 use crate::error::Error;
 use crate::exchanges::{GetExchangeMarketsRequest, GetExchangeRequest, GetExchangesRequest};
 use crate::global::GetGlobalRequest;
+use crate::id::{CoinId, ExchangeId, PersonId, PlatformId, TagId};
 use crate::key::GetKeyInfoRequest;
+use crate::number::Amount;
 use crate::people::GetPersonRequest;
+use crate::portfolio::{
+    GetCostBasisRequest, GetPortfolioHistoryRequest, GetPortfolioValuationRequest, LedgerEntry,
+    Portfolio,
+};
+use crate::pricing::{GetConversionAtRequest, GetPriceAtRequest, GetRateTableRequest, PriceCache};
 use crate::tags::{GetTagRequest, GetTagsRequest};
-use crate::tickers::{GetHistoricalTicksRequest, GetTickerRequest, GetTickersRequest};
+use crate::tickers::{
+    GetHistoricalTicksRangeRequest, GetHistoricalTicksRequest, GetTickerRequest, GetTickersRequest,
+};
 use crate::tools::{GetPriceConversionRequest, GetSearchRequest};
+use chrono::{DateTime, Utc};
 use reqwest::StatusCode;
 use reqwest_middleware::{
     ClientBuilder, ClientWithMiddleware, Error as ReqwestMiddlewareError, RequestBuilder,
 };
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
+use serde::de::DeserializeOwned;
 
 static DEFAULT_USER_AGENT: &str = "coinpaprika-api-rust-client";
 static API_URL: &str = "https://api.coinpaprika.com/v1/";
@@ -32,12 +49,23 @@ pub struct Response {
     pub request: reqwest::Request,
 }
 
+impl Response {
+    /// Deserializes the response body as JSON. On failure returns [`Error::Decode`] with the JSON
+    /// path of the value that failed and an excerpt of the body.
+    pub async fn json<T: DeserializeOwned>(self) -> Result<T, Error> {
+        let body = self.response.bytes().await?;
+
+        decode::from_slice(self.request.url().path(), &body)
+    }
+}
+
 /// Client struct used for connecting with coinpaprika.com
 pub struct Client {
     pub client: ClientWithMiddleware,
     pub api_url: &'static str,
     api_key: Option<String>,
     user_agent: &'static str,
+    pub(crate) price_cache: PriceCache,
 }
 
 impl Client {
@@ -51,6 +79,7 @@ impl Client {
             api_url: API_URL,
             api_key: None,
             user_agent: DEFAULT_USER_AGENT,
+            price_cache: PriceCache::default(),
         }
     }
 
@@ -65,6 +94,7 @@ impl Client {
             api_url: API_URL_PRO,
             api_key: Some(String::from(key)),
             user_agent: DEFAULT_USER_AGENT,
+            price_cache: PriceCache::default(),
         }
     }
 
@@ -116,7 +146,7 @@ impl Client {
     // Key
     //
     /// Call to [/key/info](https://api.coinpaprika.com/#tag/Key/paths/~1key~1info/get)
-    pub fn key_info(&self) -> GetKeyInfoRequest {
+    pub fn key_info(&self) -> GetKeyInfoRequest<'_> {
         GetKeyInfoRequest::new(self)
     }
 
@@ -124,7 +154,7 @@ impl Client {
     // Global
     //
     /// Call to [/global](https://api.coinpaprika.com/#tag/Global/paths/~1global/get)
-    pub fn global(&self) -> GetGlobalRequest {
+    pub fn global(&self) -> GetGlobalRequest<'_> {
         GetGlobalRequest::new(self)
     }
 
@@ -132,53 +162,71 @@ impl Client {
     // Coins
     //
     /// Call to [/coins](https://api.coinpaprika.com/#tag/Coins/paths/~1coins/get)
-    pub fn coins(&self) -> GetCoinsRequest {
+    pub fn coins(&self) -> GetCoinsRequest<'_> {
         GetCoinsRequest::new(self)
     }
 
     /// Call to [/coins/{coin_id}](https://api.coinpaprika.com/#tag/Coins/operation/getCoinById)
-    pub fn coin(&self, coin_id: &str) -> GetCoinRequest {
+    pub fn coin(&self, coin_id: impl Into<CoinId>) -> GetCoinRequest<'_> {
         GetCoinRequest::new(self, coin_id)
     }
 
     /// Call to
     /// [/coins/{coin_id}/twitter](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1twitter/get)
-    pub fn twitter(&self, coin_id: &str) -> GetTwitterRequest {
+    pub fn twitter(&self, coin_id: impl Into<CoinId>) -> GetTwitterRequest<'_> {
         GetTwitterRequest::new(self, coin_id)
     }
 
     /// Call to
     /// [/coins/{coin_id}/events](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1events/get)
-    pub fn coin_events(&self, coin_id: &str) -> GetCoinEventsRequest {
+    pub fn coin_events(&self, coin_id: impl Into<CoinId>) -> GetCoinEventsRequest<'_> {
         GetCoinEventsRequest::new(self, coin_id)
     }
 
     /// Call to
     /// [/coins/{coin_id}/exchanges](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1exchanges/get)
-    pub fn coin_exchanges(&self, coin_id: &str) -> GetCoinExchangesRequest {
+    pub fn coin_exchanges(&self, coin_id: impl Into<CoinId>) -> GetCoinExchangesRequest<'_> {
         GetCoinExchangesRequest::new(self, coin_id)
     }
 
     /// Call to [/coins/{coin_id}/markets](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1markets/get)
-    pub fn coin_markets(&self, coin_id: &str) -> GetCoinMarketsRequest {
+    pub fn coin_markets(&self, coin_id: impl Into<CoinId>) -> GetCoinMarketsRequest<'_> {
         GetCoinMarketsRequest::new(self, coin_id)
     }
 
     /// Call to
     /// [/coins/{coin_id}/ohlcv/latest](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1ohlcv~1latest~1/get)
-    pub fn coin_ohlc_last_full_day(&self, coin_id: &str) -> GetCoinOHLCLastFullDayRequest {
+    pub fn coin_ohlc_last_full_day(
+        &self,
+        coin_id: impl Into<CoinId>,
+    ) -> GetCoinOHLCLastFullDayRequest<'_> {
         GetCoinOHLCLastFullDayRequest::new(self, coin_id)
     }
 
     /// Call to
     /// [/coins/{coin_id}/ohlcv/historical](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1ohlcv~1historical/get)
-    pub fn coin_ohlc_historical(&self, coin_id: &str) -> GetCoinOHLCHistoricalRequest {
+    pub fn coin_ohlc_historical(
+        &self,
+        coin_id: impl Into<CoinId>,
+    ) -> GetCoinOHLCHistoricalRequest<'_> {
         GetCoinOHLCHistoricalRequest::new(self, coin_id)
     }
 
+    /// Daily candles between `start` and `end` from
+    /// [/coins/{coin_id}/ohlcv/historical](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1ohlcv~1historical/get),
+    /// split into as many requests as the range needs
+    pub fn coin_ohlc_history(
+        &self,
+        coin_id: impl Into<CoinId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> GetCoinOHLCHistoryRequest<'_> {
+        GetCoinOHLCHistoryRequest::new(self, coin_id, start, end)
+    }
+
     /// Call to
     /// [/coins/{coin_id}/ohlcv/today](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1ohlcv~1today~1/get)
-    pub fn coin_ohlc_today(&self, coin_id: &str) -> GetCoinOHLCTodayRequest {
+    pub fn coin_ohlc_today(&self, coin_id: impl Into<CoinId>) -> GetCoinOHLCTodayRequest<'_> {
         GetCoinOHLCTodayRequest::new(self, coin_id)
     }
 
@@ -187,7 +235,7 @@ impl Client {
     //
     /// Call to
     /// [/people/{person_id}](https://api.coinpaprika.com/#tag/People/operation/getPeopleById)
-    pub fn person(&self, person_id: &str) -> GetPersonRequest {
+    pub fn person(&self, person_id: impl Into<PersonId>) -> GetPersonRequest<'_> {
         GetPersonRequest::new(self, person_id)
     }
 
@@ -196,13 +244,13 @@ impl Client {
     //
     /// Call to
     /// [/tags](https://api.coinpaprika.com/#tag/Tags/paths/~1tags/get)
-    pub fn tags(&self) -> GetTagsRequest {
+    pub fn tags(&self) -> GetTagsRequest<'_> {
         GetTagsRequest::new(self)
     }
 
     /// Call to
     /// [/tags/{tag_id}](https://api.coinpaprika.com/#tag/Tags/paths/~1tags~1%7Btag_id%7D/get)
-    pub fn tag(&self, tag_id: &str) -> GetTagRequest {
+    pub fn tag(&self, tag_id: impl Into<TagId>) -> GetTagRequest<'_> {
         GetTagRequest::new(self, tag_id)
     }
 
@@ -210,39 +258,54 @@ impl Client {
     // Tickers
     //
     /// Call to [/tickers](https://api.coinpaprika.com/#tag/Tickers/operation/getTickers)
-    pub fn tickers(&self) -> GetTickersRequest {
+    pub fn tickers(&self) -> GetTickersRequest<'_> {
         GetTickersRequest::new(self)
     }
 
     /// Call to
     /// [/ticker/{coin_id}](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersById)
-    pub fn ticker(&self, coin_id: &str) -> GetTickerRequest {
+    pub fn ticker(&self, coin_id: impl Into<CoinId>) -> GetTickerRequest<'_> {
         GetTickerRequest::new(self, coin_id)
     }
 
     /// Call to
     /// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
-    pub fn historical_ticks(&self, coin_id: &str) -> GetHistoricalTicksRequest {
+    pub fn historical_ticks(&self, coin_id: impl Into<CoinId>) -> GetHistoricalTicksRequest<'_> {
         GetHistoricalTicksRequest::new(self, coin_id)
     }
 
+    /// Historical ticks between `start` and `end` from
+    /// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById),
+    /// split into as many requests as the range needs
+    pub fn historical_ticks_range(
+        &self,
+        coin_id: impl Into<CoinId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> GetHistoricalTicksRangeRequest<'_> {
+        GetHistoricalTicksRangeRequest::new(self, coin_id, start, end)
+    }
+
     //
     // Exchanges
     //
     /// Call to [/exchanges](https://api.coinpaprika.com/#tag/Exchanges/operation/getExchanges)
-    pub fn exchanges(&self) -> GetExchangesRequest {
+    pub fn exchanges(&self) -> GetExchangesRequest<'_> {
         GetExchangesRequest::new(self)
     }
 
     /// Call to
     /// [/exchanges/{exchange_id}](https://api.coinpaprika.com/#tag/Exchanges/operation/getExchangeByID)
-    pub fn exchange(&self, exchange_id: &str) -> GetExchangeRequest {
+    pub fn exchange(&self, exchange_id: impl Into<ExchangeId>) -> GetExchangeRequest<'_> {
         GetExchangeRequest::new(self, exchange_id)
     }
 
     /// Call to
     /// [/exchanges/{exchange_id}/markets](https://api.coinpaprika.com/#tag/Exchanges/paths/~1exchanges~1%7Bexchange_id%7D~1markets/get)
-    pub fn exchange_markets(&self, exchange_id: &str) -> GetExchangeMarketsRequest {
+    pub fn exchange_markets(
+        &self,
+        exchange_id: impl Into<ExchangeId>,
+    ) -> GetExchangeMarketsRequest<'_> {
         GetExchangeMarketsRequest::new(self, exchange_id)
     }
 
@@ -250,7 +313,7 @@ impl Client {
     // Tools
     //
     /// Call to [/search](https://api.coinpaprika.com/#tag/Tools/paths/~1search/get)
-    pub fn search(&self, q: &str) -> GetSearchRequest {
+    pub fn search(&self, q: &str) -> GetSearchRequest<'_> {
         GetSearchRequest::new(self, q)
     }
 
@@ -258,9 +321,9 @@ impl Client {
     /// [/price-converter](https://api.coinpaprika.com/#tag/Tools/paths/~1price-converter/get)
     pub fn price_convert(
         &self,
-        base_currency_id: &str,
-        quote_currency_id: &str,
-    ) -> GetPriceConversionRequest {
+        base_currency_id: impl Into<CoinId>,
+        quote_currency_id: impl Into<CoinId>,
+    ) -> GetPriceConversionRequest<'_> {
         GetPriceConversionRequest::new(self, base_currency_id, quote_currency_id)
     }
 
@@ -268,23 +331,141 @@ impl Client {
     // Contracts
     //
     /// Call to [/contracts](https://api.coinpaprika.com/#tag/Contracts/operation/getPlatforms)
-    pub fn contract_platforms(&self) -> GetContractPlatformsRequest {
+    pub fn contract_platforms(&self) -> GetContractPlatformsRequest<'_> {
         GetContractPlatformsRequest::new(self)
     }
 
     /// Call to
     /// [/contracts/{platform_id}](https://api.coinpaprika.com/#tag/Contracts/operation/getContracts)
-    pub fn contracts(&self, platform_id: &str) -> GetContractsRequest {
+    pub fn contracts(&self, platform_id: impl Into<PlatformId>) -> GetContractsRequest<'_> {
         GetContractsRequest::new(self, platform_id)
     }
 
+    /// Call to
+    /// [/contracts/{platform_id}/{contract_address}](https://api.coinpaprika.com/#tag/Contracts/operation/getTicker),
+    /// following the redirect to the ticker of the coin with the given contract address
+    pub fn ticker_by_contract(
+        &self,
+        platform_id: impl Into<PlatformId>,
+        contract_address: &str,
+    ) -> GetTickerByContractRequest<'_> {
+        GetTickerByContractRequest::new(self, platform_id, contract_address)
+    }
+
+    /// Call to
+    /// [/contracts/{platform_id}/{contract_address}/historical](https://api.coinpaprika.com/#tag/Contracts/operation/getHistoricalTicker),
+    /// following the redirect to historical ticks of the coin with the given contract address
+    pub fn historical_ticks_by_contract(
+        &self,
+        platform_id: impl Into<PlatformId>,
+        contract_address: &str,
+    ) -> GetHistoricalTicksByContractRequest<'_> {
+        GetHistoricalTicksByContractRequest::new(self, platform_id, contract_address)
+    }
+
     //
     // Changelog
     //
     /// Call to [/changelog/ids](https://api.coinpaprika.com/#tag/Changelog/operation/getChangelogIDs)
-    pub fn changelog(&self, page: i32) -> GetChangelogRequest {
+    pub fn changelog(&self, page: i32) -> GetChangelogRequest<'_> {
         GetChangelogRequest::new(self, page)
     }
+
+    /// Stream of all changes from
+    /// [/changelog/ids](https://api.coinpaprika.com/#tag/Changelog/operation/getChangelogIDs),
+    /// fetching pages as needed
+    pub fn changelog_all(&self) -> GetChangelogStreamRequest<'_> {
+        GetChangelogStreamRequest::new(self)
+    }
+
+    //
+    // Analytics
+    //
+    /// Risk and return report of a coin between `start` and `end`, computed from
+    /// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
+    pub fn coin_risk_report(
+        &self,
+        coin_id: impl Into<CoinId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> GetCoinRiskReportRequest<'_> {
+        GetCoinRiskReportRequest::new(self, coin_id, start, end)
+    }
+
+    /// Historical ticks of several coins between `start` and `end` from
+    /// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById),
+    /// aligned on a common timestamp grid
+    pub fn aligned_series<C: Into<CoinId>>(
+        &self,
+        coin_ids: Vec<C>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> GetAlignedSeriesRequest<'_> {
+        GetAlignedSeriesRequest::new(self, coin_ids, start, end)
+    }
+
+    //
+    // Pricing
+    //
+    /// Price of a coin at `at` in `quote` (`usd` or `btc`), from
+    /// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
+    pub fn price_at(
+        &self,
+        coin_id: impl Into<CoinId>,
+        at: DateTime<Utc>,
+        quote: &str,
+    ) -> GetPriceAtRequest<'_> {
+        GetPriceAtRequest::new(self, coin_id, at, quote)
+    }
+
+    /// Converts `amount` of `base_id` into `quote_id` at `at`, from the USD prices of both from
+    /// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
+    pub fn convert_at(
+        &self,
+        base_id: impl Into<CoinId>,
+        quote_id: impl Into<CoinId>,
+        amount: impl Into<Amount>,
+        at: DateTime<Utc>,
+    ) -> GetConversionAtRequest<'_> {
+        GetConversionAtRequest::new(self, base_id, quote_id, amount, at)
+    }
+
+    /// Snapshot of conversion rates between all coins and quote currencies, from
+    /// [/tickers](https://api.coinpaprika.com/#tag/Tickers/operation/getTickers)
+    pub fn rate_table(&self) -> GetRateTableRequest<'_> {
+        GetRateTableRequest::new(self)
+    }
+
+    //
+    // Portfolio
+    //
+    /// Valuation of `portfolio` from
+    /// [/tickers](https://api.coinpaprika.com/#tag/Tickers/operation/getTickers)
+    pub fn portfolio_valuation(&self, portfolio: &Portfolio) -> GetPortfolioValuationRequest<'_> {
+        GetPortfolioValuationRequest::new(self, portfolio)
+    }
+
+    /// Value of `portfolio` between `start` and `end` from
+    /// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
+    pub fn portfolio_history(
+        &self,
+        portfolio: &Portfolio,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> GetPortfolioHistoryRequest<'_> {
+        GetPortfolioHistoryRequest::new(self, portfolio, start, end)
+    }
+
+    /// Cost basis and gains of `ledger`, filling missing values with historical prices
+    pub fn cost_basis(&self, ledger: Vec<LedgerEntry>) -> GetCostBasisRequest<'_> {
+        GetCostBasisRequest::new(self, ledger)
+    }
+
+    /// Empties the cache of historical samples used by [`price_at`](Client::price_at) and
+    /// [`portfolio_history`](Client::portfolio_history)
+    pub fn clear_price_cache(&self) {
+        self.price_cache.clear();
+    }
 }
 
 impl Default for Client {
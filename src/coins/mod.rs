@@ -1,16 +1,60 @@
 use crate::client::{Client, Response};
+use crate::contracts::ContractType;
+use crate::currency::{quotes_query, QuoteCurrency};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
+use crate::decode::{lenient_list, LenientList};
 use crate::error::Error;
-use crate::exchanges::Fiat;
+use crate::exchanges::{FeeType, Fiat, MarketCategory, MarketQuote};
+use crate::id::{CoinId, ExchangeId, PersonId, PlatformId, TagId};
+use crate::number::{deserialize_option_amount, Amount, Price};
+use crate::validate::{self, HISTORICAL_QUOTES};
 use chrono::prelude::*;
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
+
+mod ohlc_range;
+pub use ohlc_range::{GetCoinOHLCHistoryRequest, OHLCGap, OHLCHistory};
+
+/// Maximum number of rows returned by a single historical OHLC request
+pub static MAX_OHLC_HISTORICAL: i32 = 366;
+
+string_enum! {
+    /// Type of the cryptocurrency
+    pub enum CoinType {
+        Coin => "coin",
+        Token => "token",
+    }
+}
+
+string_enum! {
+    /// Development status of the cryptocurrency
+    pub enum DevelopmentStatus {
+        WorkingProduct => "Working product",
+        BetaVersion => "Beta version",
+        AlphaVersion => "Alpha version",
+        Prototype => "Prototype",
+        Concept => "Concept",
+    }
+}
+
+string_enum! {
+    /// Consensus mechanism of the cryptocurrency
+    pub enum ProofType {
+        ProofOfWork => "Proof of Work",
+        ProofOfStake => "Proof of Stake",
+        DelegatedProofOfStake => "Delegated Proof of Stake",
+        ProofOfAuthority => "Proof of Authority",
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Basic information about cryptocurrencies on coinpaprika.com
 pub struct Coin {
     /// ID of coin on coinpaprika.com
-    pub id: String,
+    pub id: CoinId,
 
     /// Name of the cryptocurrency
     pub name: String,
@@ -29,23 +73,31 @@ pub struct Coin {
     pub is_active: bool,
 
     #[serde(rename = "type")]
-    /// Type of the cryptocurrency. Currently supported values are `coin` and `token`
-    pub coin_type: String,
+    /// Type of the cryptocurrency
+    pub coin_type: CoinType,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Parent coin
 pub struct Parent {
-    pub id: String,
+    pub id: CoinId,
     pub name: String,
     pub symbol: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Tag assigned to a coin
 pub struct CoinTag {
     /// ID of the tag
-    pub id: String,
+    pub id: TagId,
 
     /// Name of the tag
     pub name: String,
@@ -55,14 +107,22 @@ pub struct CoinTag {
 
     /// Number of ico projects with this tag
     pub ico_counter: i32,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// The cryptocurrency founding and/or developing team
 pub struct Team {
-    pub id: String,
+    pub id: PersonId,
     pub name: String,
     pub position: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -73,12 +133,15 @@ pub struct Contract {
 
     /// ID of the contract platform. For Ethereum contracts it is `eth-ethereum`, for Tron
     /// `trx-tron`, etc.
-    pub platform: String,
+    pub platform: PlatformId,
 
     #[serde(rename = "type")]
-    /// Type of the contract. Currently supported values are: `ERC20`, `BEP2`, `TRC10`, `TRC20`,
-    /// `Stellar Asset`, `Other`
-    pub contract_type: String,
+    /// Type of the contract
+    pub contract_type: ContractType,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -89,13 +152,17 @@ pub struct Whitepaper {
 
     /// Link to the whitepaper thumbnail
     pub thumbnail: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Detailed, descriptive information about a single coin, without price or volume data.
 pub struct CoinDetails {
     /// ID of coin on coinpaprika.com
-    pub id: String,
+    pub id: CoinId,
 
     /// Name of the cryptocurrency
     pub name: String,
@@ -117,8 +184,8 @@ pub struct CoinDetails {
     pub is_active: bool,
 
     #[serde(rename = "type")]
-    /// Type of the cryptocurrency. Currently supported values are `coin` and `token`
-    pub coin_type: String,
+    /// Type of the cryptocurrency
+    pub coin_type: CoinType,
 
     /// Logo image URL
     pub logo: String,
@@ -146,10 +213,10 @@ pub struct CoinDetails {
 
     /// Development status of the cryptocurrency - if it is a working project, beta version, just
     /// an idea, etc.
-    pub development_status: Option<String>,
+    pub development_status: Option<DevelopmentStatus>,
 
     /// Cryptocurrency proof type: Proof of Work, Proof of Stake, etc.
-    pub proof_type: Option<String>,
+    pub proof_type: Option<ProofType>,
 
     /// The cryptocurrency organization structure: centralized, decentralized, hierarchical, flat,
     /// etc.
@@ -183,6 +250,10 @@ pub struct CoinDetails {
 
     /// Date of the last available ticker data for the coin. RFC3999 (ISO-8601) format
     pub last_data_at: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -219,6 +290,10 @@ pub struct Tweet {
 
     /// Link to Youtube video shared in this tweet
     pub youtube_link: Option<String>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -232,34 +307,46 @@ pub struct CoinEvent {
     pub is_conference: bool,
     pub link: Option<String>,
     pub proof_image_link: Option<String>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Exchange where a given coin is traded.
 pub struct CoinExchange {
-    pub id: String,
+    pub id: ExchangeId,
     pub name: String,
     pub fiats: Vec<Fiat>,
     pub adjusted_volume_24h_share: f64,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Market for a given coin.
 pub struct CoinMarket {
-    pub exchange_id: String,
+    pub exchange_id: ExchangeId,
     pub exchange_name: String,
     pub pair: String,
-    pub base_currency_id: String,
+    pub base_currency_id: CoinId,
     pub base_currency_name: String,
-    pub quote_currency_id: String,
+    pub quote_currency_id: CoinId,
     pub quote_currency_name: String,
     pub market_url: Option<String>,
-    pub category: String,
-    pub fee_type: String,
+    pub category: MarketCategory,
+    pub fee_type: FeeType,
     pub outlier: bool,
     pub adjusted_volume_24h_share: f64,
-    pub quotes: Value,
+    pub quotes: HashMap<QuoteCurrency, MarketQuote>,
     pub last_updated: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -271,12 +358,36 @@ pub struct CoinOHLC {
     /// RFC3999 (ISO-8601) format
     pub time_close: String,
 
-    pub open: Option<f64>,
-    pub high: Option<f64>,
-    pub low: Option<f64>,
-    pub close: Option<f64>,
-    pub volume: Option<i64>,
-    pub market_cap: Option<i64>,
+    pub open: Option<Price>,
+    pub high: Option<Price>,
+    pub low: Option<Price>,
+    pub close: Option<Price>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub volume: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub market_cap: Option<Amount>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl CoinOHLC {
+    /// Parsed `time_open`, `None` if it is not a valid RFC3339 date
+    pub fn open_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.time_open)
+            .ok()
+            .map(|time| time.with_timezone(&Utc))
+    }
+
+    /// Parsed `time_close`, `None` if it is not a valid RFC3339 date
+    pub fn close_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.time_close)
+            .ok()
+            .map(|time| time.with_timezone(&Utc))
+    }
 }
 
 /// Request for getting basic information about cryptocurrencies on coinpaprika.com:
@@ -291,17 +402,27 @@ impl<'a> GetCoinsRequest<'a> {
     }
 
     pub async fn send(&self) -> Result<Vec<Coin>, Error> {
-        let request: RequestBuilder = self
-            .client
-            .client
-            .get(format!("{}/coins", self.client.api_url));
-
-        let response: Response = self.client.request(request).await?;
+        let response: Response = self.client.request(self.request()?).await?;
 
-        let data: Vec<Coin> = response.response.json().await?;
+        let data: Vec<Coin> = response.json().await?;
 
         Ok(data)
     }
+
+    /// Like [`send`](Self::send), but decodes the response item by item. Items that fail to decode
+    /// are skipped and reported in [`LenientList::diagnostics`].
+    pub async fn send_lenient(&self) -> Result<LenientList<Coin>, Error> {
+        let response: Response = self.client.request(self.request()?).await?;
+
+        lenient_list(response).await
+    }
+
+    fn request(&self) -> Result<RequestBuilder, Error> {
+        Ok(self
+            .client
+            .client
+            .get(format!("{}/coins", self.client.api_url)))
+    }
 }
 
 /// Request for getting detailed, descriptive information about a single coin, without price or
@@ -309,26 +430,27 @@ impl<'a> GetCoinsRequest<'a> {
 /// [/coins/{coin_id}](https://api.coinpaprika.com/#tag/Coins/operation/getCoinById)
 pub struct GetCoinRequest<'a> {
     client: &'a Client,
-    coin_id: String,
+    coin_id: CoinId,
 }
 
 impl<'a> GetCoinRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
         }
     }
 
     pub async fn send(&self) -> Result<CoinDetails, Error> {
-        let request: RequestBuilder = self
-            .client
-            .client
-            .get(format!("{}/coins/{}", self.client.api_url, self.coin_id));
+        let request: RequestBuilder = self.client.client.get(format!(
+            "{}/coins/{}",
+            self.client.api_url,
+            self.coin_id.encoded()
+        ));
 
         let response: Response = self.client.request(request).await?;
 
-        let data: CoinDetails = response.response.json().await?;
+        let data: CoinDetails = response.json().await?;
 
         Ok(data)
     }
@@ -338,26 +460,27 @@ impl<'a> GetCoinRequest<'a> {
 /// [/coins/{coin_id}/twitter](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1twitter/get)
 pub struct GetTwitterRequest<'a> {
     client: &'a Client,
-    coin_id: String,
+    coin_id: CoinId,
 }
 
 impl<'a> GetTwitterRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
         }
     }
 
     pub async fn send(&self) -> Result<Vec<Tweet>, Error> {
         let request: RequestBuilder = self.client.client.get(format!(
             "{}/coins/{}/twitter",
-            self.client.api_url, self.coin_id
+            self.client.api_url,
+            self.coin_id.encoded()
         ));
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<Tweet> = response.response.json().await?;
+        let data: Vec<Tweet> = response.json().await?;
 
         Ok(data)
     }
@@ -367,26 +490,27 @@ impl<'a> GetTwitterRequest<'a> {
 /// [/coins/{coin_id}/events](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1events/get)
 pub struct GetCoinEventsRequest<'a> {
     client: &'a Client,
-    coin_id: String,
+    coin_id: CoinId,
 }
 
 impl<'a> GetCoinEventsRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
         }
     }
 
     pub async fn send(&self) -> Result<Vec<CoinEvent>, Error> {
         let request: RequestBuilder = self.client.client.get(format!(
             "{}/coins/{}/events",
-            self.client.api_url, self.coin_id
+            self.client.api_url,
+            self.coin_id.encoded()
         ));
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<CoinEvent> = response.response.json().await?;
+        let data: Vec<CoinEvent> = response.json().await?;
 
         Ok(data)
     }
@@ -396,26 +520,27 @@ impl<'a> GetCoinEventsRequest<'a> {
 /// [/coins/{coin_id}/exchanges](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1exchanges/get)
 pub struct GetCoinExchangesRequest<'a> {
     client: &'a Client,
-    coin_id: String,
+    coin_id: CoinId,
 }
 
 impl<'a> GetCoinExchangesRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
         }
     }
 
     pub async fn send(&self) -> Result<Vec<CoinExchange>, Error> {
         let request: RequestBuilder = self.client.client.get(format!(
             "{}/coins/{}/exchanges",
-            self.client.api_url, self.coin_id
+            self.client.api_url,
+            self.coin_id.encoded()
         ));
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<CoinExchange> = response.response.json().await?;
+        let data: Vec<CoinExchange> = response.json().await?;
 
         Ok(data)
     }
@@ -425,46 +550,45 @@ impl<'a> GetCoinExchangesRequest<'a> {
 /// [/coins/{coin_id}/markets](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1markets/get)
 pub struct GetCoinMarketsRequest<'a> {
     client: &'a Client,
-    coin_id: String,
-    quotes: Vec<String>,
+    coin_id: CoinId,
+    quotes: Vec<QuoteCurrency>,
 }
 
 impl<'a> GetCoinMarketsRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
             quotes: vec![],
         }
     }
 
-    /// List of quotes to return. Up to 3 quotes at once. Currently allowed values:
-    /// BTC, ETH, USD, EUR, PLN, KRW, GBP, CAD, JPY, RUB, TRY, NZD, AUD, CHF, UAH, HKD, SGD, NGN,
-    /// PHP, MXN, BRL, THB, CLP, CNY, CZK, DKK, HUF, IDR, ILS, INR, MYR, NOK, PKR, SEK, TWD, ZAR,
-    /// VND, BOB, COP, PEN, ARS, ISK
-    pub fn quotes(&mut self, quotes: Vec<&str>) -> &'a mut GetCoinMarketsRequest {
-        self.quotes = quotes.iter().map(|&q| String::from(q)).collect();
+    /// List of quotes to return. Up to 3 quotes at once, see [`QuoteCurrency`] for currently
+    /// allowed values.
+    pub fn quotes<Q: Into<QuoteCurrency>>(
+        &mut self,
+        quotes: Vec<Q>,
+    ) -> &'a mut GetCoinMarketsRequest<'_> {
+        self.quotes = quotes.into_iter().map(Into::into).collect();
         self
     }
 
     pub async fn send(&self) -> Result<Vec<CoinMarket>, Error> {
-        let query = match self.quotes.len() {
-            0 => vec![],
-            _ => vec![("quotes", self.quotes.join(","))],
-        };
+        let query = quotes_query(&self.quotes)?;
 
         let request: RequestBuilder = self
             .client
             .client
             .get(format!(
                 "{}/coins/{}/markets",
-                self.client.api_url, self.coin_id
+                self.client.api_url,
+                self.coin_id.encoded()
             ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<CoinMarket> = response.response.json().await?;
+        let data: Vec<CoinMarket> = response.json().await?;
 
         Ok(data)
     }
@@ -475,27 +599,31 @@ impl<'a> GetCoinMarketsRequest<'a> {
 /// [/coins/{coin_id}/ohlcv/latest](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1ohlcv~1latest~1/get)
 pub struct GetCoinOHLCLastFullDayRequest<'a> {
     client: &'a Client,
-    coin_id: String,
+    coin_id: CoinId,
     quote: Option<String>,
 }
 
 impl<'a> GetCoinOHLCLastFullDayRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
             quote: None,
         }
     }
     /// Returned data quote (available values: `usd` `btc`)
     ///
     /// Default: `"usd"`
-    pub fn quote(&mut self, quote: &str) -> &'a mut GetCoinOHLCLastFullDayRequest {
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetCoinOHLCLastFullDayRequest<'_> {
         self.quote = Some(String::from(quote));
         self
     }
 
     pub async fn send(&self) -> Result<Vec<CoinOHLC>, Error> {
+        if let Some(quote) = &self.quote {
+            validate::one_of("quote", quote, &HISTORICAL_QUOTES)?;
+        }
+
         let mut query: Vec<(&str, &str)> = Vec::new();
 
         if let Some(quote) = &self.quote {
@@ -507,13 +635,14 @@ impl<'a> GetCoinOHLCLastFullDayRequest<'a> {
             .client
             .get(format!(
                 "{}/coins/{}/ohlcv/latest",
-                self.client.api_url, self.coin_id
+                self.client.api_url,
+                self.coin_id.encoded()
             ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<CoinOHLC> = response.response.json().await?;
+        let data: Vec<CoinOHLC> = response.json().await?;
 
         Ok(data)
     }
@@ -525,20 +654,20 @@ impl<'a> GetCoinOHLCLastFullDayRequest<'a> {
 /// [/coins/{coin_id}/ohlcv/historical](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1ohlcv~1historical/get)
 pub struct GetCoinOHLCHistoricalRequest<'a> {
     client: &'a Client,
-    coin_id: String,
+    coin_id: CoinId,
     start: String,
     end: Option<String>,
-    limit: Option<String>,
+    limit: Option<i32>,
     quote: Option<String>,
 }
 
 impl<'a> GetCoinOHLCHistoricalRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         let now: DateTime<Utc> = Utc::now(); // e.g. `2014-11-28T12:45:59.324310806Z`
 
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
             start: format!("{}-{}-{}", now.year(), now.month(), now.day()),
             end: None,
             limit: None,
@@ -552,7 +681,7 @@ impl<'a> GetCoinOHLCHistoricalRequest<'a> {
     /// * RFC3999 (ISO-8601) eg. 2018-02-15T05:15:00Z
     /// * Simple date (yyyy-mm-dd) eg. 2018-02-15
     /// * Unix timestamp (in seconds) eg. 1518671700
-    pub fn start(&mut self, start: &str) -> &'a mut GetCoinOHLCHistoricalRequest {
+    pub fn start(&mut self, start: &str) -> &'a mut GetCoinOHLCHistoricalRequest<'_> {
         self.start = String::from(start);
         self
     }
@@ -565,7 +694,7 @@ impl<'a> GetCoinOHLCHistoricalRequest<'a> {
     /// RFC3999 (ISO-8601) eg. 2018-02-15T05:15:00Z
     /// Simple date (yyyy-mm-dd) eg. 2018-02-15
     /// Unix timestamp (in seconds) eg. 1518671700
-    pub fn end(&mut self, end: &str) -> &'a mut GetCoinOHLCHistoricalRequest {
+    pub fn end(&mut self, end: &str) -> &'a mut GetCoinOHLCHistoricalRequest<'_> {
         self.end = Some(String::from(end));
         self
     }
@@ -573,27 +702,36 @@ impl<'a> GetCoinOHLCHistoricalRequest<'a> {
     /// Limit of result rows (max `366`)
     ///
     /// Default: `1`
-    pub fn limit(&mut self, limit: i32) -> &'a mut GetCoinOHLCHistoricalRequest {
-        self.limit = Some(limit.to_string());
+    pub fn limit(&mut self, limit: i32) -> &'a mut GetCoinOHLCHistoricalRequest<'_> {
+        self.limit = Some(limit);
         self
     }
 
     /// Returned data quote (available values: `usd` `btc`)
     ///
     /// Default: `"usd"`
-    pub fn quote(&mut self, quote: &str) -> &'a mut GetCoinOHLCHistoricalRequest {
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetCoinOHLCHistoricalRequest<'_> {
         self.quote = Some(String::from(quote));
         self
     }
 
     pub async fn send(&self) -> Result<Vec<CoinOHLC>, Error> {
+        if let Some(limit) = self.limit {
+            validate::range("limit", limit, 1, MAX_OHLC_HISTORICAL)?;
+        }
+
+        if let Some(quote) = &self.quote {
+            validate::one_of("quote", quote, &HISTORICAL_QUOTES)?;
+        }
+
+        let limit = self.limit.map(|limit| limit.to_string());
         let mut query: Vec<(&str, &str)> = vec![("start", self.start.as_ref())];
 
         if let Some(end) = &self.end {
             query.push(("end", end));
         }
 
-        if let Some(limit) = &self.limit {
+        if let Some(limit) = &limit {
             query.push(("limit", limit));
         }
 
@@ -606,13 +744,14 @@ impl<'a> GetCoinOHLCHistoricalRequest<'a> {
             .client
             .get(format!(
                 "{}/coins/{}/ohlcv/historical",
-                self.client.api_url, self.coin_id
+                self.client.api_url,
+                self.coin_id.encoded()
             ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<CoinOHLC> = response.response.json().await?;
+        let data: Vec<CoinOHLC> = response.json().await?;
 
         Ok(data)
     }
@@ -623,15 +762,15 @@ impl<'a> GetCoinOHLCHistoricalRequest<'a> {
 /// [/coins/{coin_id}/ohlcv/today](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1ohlcv~1today~1/get)
 pub struct GetCoinOHLCTodayRequest<'a> {
     client: &'a Client,
-    coin_id: String,
+    coin_id: CoinId,
     quote: Option<String>,
 }
 
 impl<'a> GetCoinOHLCTodayRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
             quote: None,
         }
     }
@@ -639,12 +778,16 @@ impl<'a> GetCoinOHLCTodayRequest<'a> {
     /// Returned data quote (available values: `usd` `btc`)
     ///
     /// Default: `"usd"`
-    pub fn quote(&mut self, quote: &str) -> &'a mut GetCoinOHLCTodayRequest {
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetCoinOHLCTodayRequest<'_> {
         self.quote = Some(String::from(quote));
         self
     }
 
     pub async fn send(&self) -> Result<Vec<CoinOHLC>, Error> {
+        if let Some(quote) = &self.quote {
+            validate::one_of("quote", quote, &HISTORICAL_QUOTES)?;
+        }
+
         let mut query: Vec<(&str, &str)> = Vec::new();
 
         if let Some(quote) = &self.quote {
@@ -656,13 +799,14 @@ impl<'a> GetCoinOHLCTodayRequest<'a> {
             .client
             .get(format!(
                 "{}/coins/{}/ohlcv/today",
-                self.client.api_url, self.coin_id
+                self.client.api_url,
+                self.coin_id.encoded()
             ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<CoinOHLC> = response.response.json().await?;
+        let data: Vec<CoinOHLC> = response.json().await?;
 
         Ok(data)
     }
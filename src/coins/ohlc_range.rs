@@ -0,0 +1,130 @@
+use crate::client::Client;
+use crate::coins::{CoinOHLC, MAX_OHLC_HISTORICAL};
+use crate::error::Error;
+use crate::id::CoinId;
+use crate::window;
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+#[derive(Debug)]
+/// Daily candles over a time range, sorted by `time_open` and without duplicates
+pub struct OHLCHistory {
+    pub candles: Vec<CoinOHLC>,
+
+    /// Missing days between two consecutive candles
+    pub gaps: Vec<OHLCGap>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Run of missing daily candles
+pub struct OHLCGap {
+    /// `time_open` of the last candle before the gap
+    pub after: DateTime<Utc>,
+
+    /// `time_open` of the first candle after the gap
+    pub before: DateTime<Utc>,
+
+    /// Number of missing candles
+    pub missing: i64,
+}
+
+/// Request for getting daily Open/High/Low/Close values over an arbitrary date range, split into
+/// as many
+/// [/coins/{coin_id}/ohlcv/historical](https://api.coinpaprika.com/#tag/Coins/paths/~1coins~1%7Bcoin_id%7D~1ohlcv~1historical/get)
+/// requests as needed to stay within [`MAX_OHLC_HISTORICAL`] rows each
+pub struct GetCoinOHLCHistoryRequest<'a> {
+    client: &'a Client,
+    coin_id: CoinId,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    quote: String,
+    concurrency: usize,
+}
+
+impl<'a> GetCoinOHLCHistoryRequest<'a> {
+    pub fn new(
+        client: &'a Client,
+        coin_id: impl Into<CoinId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            client,
+            coin_id: coin_id.into(),
+            start,
+            end,
+            quote: String::from("usd"),
+            concurrency: 1,
+        }
+    }
+
+    /// Returned data quote (available values: `usd` `btc`)
+    ///
+    /// Default: `"usd"`
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetCoinOHLCHistoryRequest<'_> {
+        self.quote = String::from(quote);
+        self
+    }
+
+    /// Maximum number of window requests in flight at once, at least 1
+    ///
+    /// Default: `1`
+    pub fn concurrency(&mut self, concurrency: usize) -> &'a mut GetCoinOHLCHistoryRequest<'_> {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Windows of at most [`MAX_OHLC_HISTORICAL`] days covering the range, in order. Both bounds
+    /// of a window are inclusive.
+    pub fn windows(&self) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        window::split(
+            self.start,
+            self.end,
+            Duration::days(i64::from(MAX_OHLC_HISTORICAL)),
+        )
+    }
+
+    pub async fn send(&self) -> Result<OHLCHistory, Error> {
+        let chunks: Vec<Vec<CoinOHLC>> = stream::iter(self.windows())
+            .map(|(start, end)| async move {
+                self.client
+                    .coin_ohlc_historical(&self.coin_id)
+                    .start(&start.timestamp().to_string())
+                    .end(&end.timestamp().to_string())
+                    .limit(MAX_OHLC_HISTORICAL)
+                    .quote(&self.quote)
+                    .send()
+                    .await
+            })
+            .buffered(self.concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let mut candles: Vec<CoinOHLC> = chunks.into_iter().flatten().collect();
+        candles.sort_by(|a, b| a.time_open.cmp(&b.time_open));
+        candles.dedup_by(|a, b| a.time_open == b.time_open);
+
+        let gaps = find_gaps(&candles);
+
+        Ok(OHLCHistory { candles, gaps })
+    }
+}
+
+/// Gaps of more than a day between consecutive candles. Candles with unparsable `time_open` are
+/// ignored.
+fn find_gaps(candles: &[CoinOHLC]) -> Vec<OHLCGap> {
+    let opens: Vec<DateTime<Utc>> = candles.iter().filter_map(CoinOHLC::open_datetime).collect();
+
+    opens
+        .windows(2)
+        .filter_map(|pair| {
+            let missing = (pair[1] - pair[0]).num_days() - 1;
+
+            (missing > 0).then_some(OHLCGap {
+                after: pair[0],
+                before: pair[1],
+                missing,
+            })
+        })
+        .collect()
+}
@@ -1,16 +1,36 @@
 use crate::client::{Client, Response};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
 use crate::error::Error;
+use crate::id::{encode_path_segment, CoinId, PlatformId};
+use crate::tickers::{HistoricalTick, HistoricalTicksQuery, Interval, Ticker};
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
+string_enum! {
+    /// Type of a contract
+    pub enum ContractType {
+        Erc20 => "ERC20",
+        Bep2 => "BEP2",
+        Trc10 => "TRC10",
+        Trc20 => "TRC20",
+        StellarAsset => "Stellar Asset",
+        Other => "Other",
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 /// Contract information
 pub struct Contract {
     pub address: String,
-    pub id: String,
+    pub id: CoinId,
 
     #[serde(rename = "type")]
-    pub contract_type: String,
+    pub contract_type: ContractType,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Request for getting all available contract platforms on coinpaprika.com
@@ -24,7 +44,7 @@ impl<'a> GetContractPlatformsRequest<'a> {
         Self { client }
     }
 
-    pub async fn send(&self) -> Result<Vec<String>, Error> {
+    pub async fn send(&self) -> Result<Vec<PlatformId>, Error> {
         let request: RequestBuilder = self
             .client
             .client
@@ -32,7 +52,7 @@ impl<'a> GetContractPlatformsRequest<'a> {
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<String> = response.response.json().await?;
+        let data: Vec<PlatformId> = response.json().await?;
 
         Ok(data)
     }
@@ -42,26 +62,162 @@ impl<'a> GetContractPlatformsRequest<'a> {
 /// [/contracts/{platform_id}](https://api.coinpaprika.com/#tag/Contracts/operation/getContracts)
 pub struct GetContractsRequest<'a> {
     client: &'a Client,
-    platform_id: String,
+    platform_id: PlatformId,
 }
 
 impl<'a> GetContractsRequest<'a> {
-    pub fn new(client: &'a Client, platform_id: &str) -> Self {
+    pub fn new(client: &'a Client, platform_id: impl Into<PlatformId>) -> Self {
         Self {
             client,
-            platform_id: String::from(platform_id),
+            platform_id: platform_id.into(),
         }
     }
 
     pub async fn send(&self) -> Result<Vec<Contract>, Error> {
         let request: RequestBuilder = self.client.client.get(format!(
             "{}/contracts/{}",
-            self.client.api_url, self.platform_id
+            self.client.api_url,
+            self.platform_id.encoded()
+        ));
+
+        let response: Response = self.client.request(request).await?;
+
+        let data: Vec<Contract> = response.json().await?;
+
+        Ok(data)
+    }
+}
+
+/// Request for getting ticker data of the coin with a given contract address on coinpaprika.com
+/// [/contracts/{platform_id}/{contract_address}](https://api.coinpaprika.com/#tag/Contracts/operation/getTicker)
+pub struct GetTickerByContractRequest<'a> {
+    client: &'a Client,
+    platform_id: PlatformId,
+    contract_address: String,
+}
+
+impl<'a> GetTickerByContractRequest<'a> {
+    pub fn new(
+        client: &'a Client,
+        platform_id: impl Into<PlatformId>,
+        contract_address: &str,
+    ) -> Self {
+        Self {
+            client,
+            platform_id: platform_id.into(),
+            contract_address: String::from(contract_address),
+        }
+    }
+
+    pub async fn send(&self) -> Result<Ticker, Error> {
+        let request: RequestBuilder = self.client.client.get(format!(
+            "{}/contracts/{}/{}",
+            self.client.api_url,
+            self.platform_id.encoded(),
+            encode_path_segment(&self.contract_address)
         ));
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<Contract> = response.response.json().await?;
+        let data: Ticker = response.json().await?;
+
+        Ok(data)
+    }
+}
+
+/// Request for getting historical ticker data of the coin with a given contract address on
+/// coinpaprika.com
+/// [/contracts/{platform_id}/{contract_address}/historical](https://api.coinpaprika.com/#tag/Contracts/operation/getHistoricalTicker)
+pub struct GetHistoricalTicksByContractRequest<'a> {
+    client: &'a Client,
+    platform_id: PlatformId,
+    contract_address: String,
+    query: HistoricalTicksQuery,
+}
+
+impl<'a> GetHistoricalTicksByContractRequest<'a> {
+    pub fn new(
+        client: &'a Client,
+        platform_id: impl Into<PlatformId>,
+        contract_address: &str,
+    ) -> Self {
+        Self {
+            client,
+            platform_id: platform_id.into(),
+            contract_address: String::from(contract_address),
+            query: HistoricalTicksQuery::new(),
+        }
+    }
+
+    /// Start point for historical data
+    ///
+    /// Supported formats:
+    /// * RFC3999 (ISO-8601) eg. 2018-02-15T05:15:00Z
+    /// * Simple date (yyyy-mm-dd) eg. 2018-02-15
+    /// * Unix timestamp (in seconds) eg. 1518671700
+    pub fn start(&mut self, start: &str) -> &'a mut GetHistoricalTicksByContractRequest<'_> {
+        self.query.start = String::from(start);
+        self
+    }
+
+    /// End point for historical data
+    ///
+    /// Default: `"NOW"`
+    ///
+    /// Supported formats:
+    /// RFC3999 (ISO-8601) eg. 2018-02-15T05:15:00Z
+    /// Simple date (yyyy-mm-dd) eg. 2018-02-15
+    /// Unix timestamp (in seconds) eg. 1518671700
+    pub fn end(&mut self, end: &str) -> &'a mut GetHistoricalTicksByContractRequest<'_> {
+        self.query.end = Some(String::from(end));
+        self
+    }
+
+    /// Limit of result rows (max `5000`)
+    ///
+    /// Default: `1000`
+    pub fn limit(&mut self, limit: i32) -> &'a mut GetHistoricalTicksByContractRequest<'_> {
+        self.query.limit = Some(limit);
+        self
+    }
+
+    /// Returned data quote (available values: `usd` `btc`)
+    ///
+    /// Default: `"usd"`
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetHistoricalTicksByContractRequest<'_> {
+        self.query.quote = Some(String::from(quote));
+        self
+    }
+
+    /// Returned points interval. Use [`HistoricalTicksPlan`](crate::tickers::HistoricalTicksPlan)
+    /// to pick an interval for a given time range.
+    ///
+    /// Default: [`Interval::Minutes5`]
+    pub fn interval(
+        &mut self,
+        interval: Interval,
+    ) -> &'a mut GetHistoricalTicksByContractRequest<'_> {
+        self.query.interval = Some(interval);
+        self
+    }
+
+    pub async fn send(&self) -> Result<Vec<HistoricalTick>, Error> {
+        let query = self.query.to_query()?;
+
+        let request: RequestBuilder = self
+            .client
+            .client
+            .get(format!(
+                "{}/contracts/{}/{}/historical",
+                self.client.api_url,
+                self.platform_id.encoded(),
+                encode_path_segment(&self.contract_address)
+            ))
+            .query(&query);
+
+        let response: Response = self.client.request(request).await?;
+
+        let data: Vec<HistoricalTick> = response.json().await?;
 
         Ok(data)
     }
@@ -0,0 +1,80 @@
+use crate::error::Error;
+
+/// Maximum number of quotes that can be requested at once
+pub static MAX_QUOTES: usize = 3;
+
+string_enum! {
+    /// Quote currency in which prices and volumes can be returned
+    pub enum QuoteCurrency {
+        Btc => "BTC",
+        Eth => "ETH",
+        Usd => "USD",
+        Eur => "EUR",
+        Pln => "PLN",
+        Krw => "KRW",
+        Gbp => "GBP",
+        Cad => "CAD",
+        Jpy => "JPY",
+        Rub => "RUB",
+        Try => "TRY",
+        Nzd => "NZD",
+        Aud => "AUD",
+        Chf => "CHF",
+        Uah => "UAH",
+        Hkd => "HKD",
+        Sgd => "SGD",
+        Ngn => "NGN",
+        Php => "PHP",
+        Mxn => "MXN",
+        Brl => "BRL",
+        Thb => "THB",
+        Clp => "CLP",
+        Cny => "CNY",
+        Czk => "CZK",
+        Dkk => "DKK",
+        Huf => "HUF",
+        Idr => "IDR",
+        Ils => "ILS",
+        Inr => "INR",
+        Myr => "MYR",
+        Nok => "NOK",
+        Pkr => "PKR",
+        Sek => "SEK",
+        Twd => "TWD",
+        Zar => "ZAR",
+        Vnd => "VND",
+        Bob => "BOB",
+        Cop => "COP",
+        Pen => "PEN",
+        Ars => "ARS",
+        Isk => "ISK",
+    }
+}
+
+/// Builds the `quotes` query parameter, rejecting more than [`MAX_QUOTES`] quotes.
+pub(crate) fn quotes_query(quotes: &[QuoteCurrency]) -> Result<Vec<(&'static str, String)>, Error> {
+    if quotes.len() > MAX_QUOTES {
+        return Err(Error::Validation {
+            field: "quotes",
+            reason: format!(
+                "up to {} quotes can be requested at once, got {}",
+                MAX_QUOTES,
+                quotes.len()
+            ),
+        });
+    }
+
+    let query = match quotes.len() {
+        0 => vec![],
+        _ => vec![(
+            "quotes",
+            quotes
+                .iter()
+                .map(QuoteCurrency::as_str)
+                .collect::<Vec<&str>>()
+                .join(","),
+        )],
+    };
+
+    Ok(query)
+}
@@ -0,0 +1,144 @@
+use crate::client::Response;
+use crate::error::Error;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Fields returned by the API that are not declared in the model holding them, kept in the `extra`
+/// field of every response model when the `extra-fields` feature is enabled
+pub type Extra = Map<String, Value>;
+
+/// Number of bytes of the response body shown on each side of the failure position
+static EXCERPT_RADIUS: usize = 80;
+
+#[derive(Debug, Clone)]
+/// Failure to decode an API response into the expected model
+pub struct DecodeError {
+    /// Path of the requested endpoint, e.g. `/v1/tickers`
+    pub endpoint: String,
+
+    /// JSON path of the value that failed to decode, e.g. `[1532].quotes.USD.ath_date`
+    pub path: String,
+
+    /// Decoding error message, e.g. `invalid type: null, expected a string at line 1 column 42`
+    pub message: String,
+
+    /// Fragment of the raw response body around the failure position
+    pub excerpt: String,
+}
+
+impl std::error::Error for DecodeError {}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to decode {} at {}: {} (body: `{}`)",
+            self.endpoint, self.path, self.message, self.excerpt
+        )
+    }
+}
+
+#[derive(Debug)]
+/// List endpoint response decoded item by item
+pub struct LenientList<T> {
+    /// Items that were decoded successfully, in the order returned by the API
+    pub items: Vec<T>,
+
+    /// Diagnostics for items that could not be decoded
+    pub diagnostics: Vec<DecodeDiagnostic>,
+}
+
+#[derive(Debug, Clone)]
+/// Reason why a single list item could not be decoded
+pub struct DecodeDiagnostic {
+    /// Position of the item in the returned list
+    pub index: usize,
+
+    /// Value of the `id` field of the item, if present
+    pub id: Option<String>,
+
+    /// JSON path of the value that failed to decode, e.g. `[1532].quotes.USD.ath_date`
+    pub path: String,
+
+    /// Decoding error message
+    pub error: String,
+}
+
+impl std::fmt::Display for DecodeDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "{} (id: {}): {}", self.path, id, self.error),
+            None => write!(f, "{}: {}", self.path, self.error),
+        }
+    }
+}
+
+/// Decodes a JSON array response item by item, collecting diagnostics for items that fail instead
+/// of failing the whole response.
+pub(crate) async fn lenient_list<T: DeserializeOwned>(
+    response: Response,
+) -> Result<LenientList<T>, Error> {
+    let values: Vec<Value> = response.json().await?;
+
+    let mut list = LenientList {
+        items: Vec::with_capacity(values.len()),
+        diagnostics: vec![],
+    };
+
+    for (index, value) in values.into_iter().enumerate() {
+        let id = value.get("id").and_then(Value::as_str).map(String::from);
+
+        match serde_path_to_error::deserialize::<_, T>(value) {
+            Ok(item) => list.items.push(item),
+            Err(err) => list.diagnostics.push(DecodeDiagnostic {
+                index,
+                id,
+                path: item_path(index, &err.path().to_string()),
+                error: err.into_inner().to_string(),
+            }),
+        }
+    }
+
+    Ok(list)
+}
+
+/// Prefixes a path relative to a list item with the item index.
+fn item_path(index: usize, path: &str) -> String {
+    match path {
+        "." => format!("[{}]", index),
+        path if path.starts_with('[') => format!("[{}]{}", index, path),
+        path => format!("[{}].{}", index, path),
+    }
+}
+
+/// Deserializes `body` returned by `endpoint`, reporting the JSON path of the value that failed.
+pub(crate) fn from_slice<T: DeserializeOwned>(endpoint: &str, body: &[u8]) -> Result<T, Error> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        let path = err.path().to_string();
+        let err = err.into_inner();
+
+        Error::Decode(Box::new(DecodeError {
+            endpoint: endpoint.to_string(),
+            path,
+            message: err.to_string(),
+            excerpt: excerpt(body, err.line(), err.column()),
+        }))
+    })
+}
+
+/// Returns the part of `body` around the given 1-based line and column.
+fn excerpt(body: &[u8], line: usize, column: usize) -> String {
+    let line_start: usize = body
+        .split(|&b| b == b'\n')
+        .take(line.saturating_sub(1))
+        .map(|l| l.len() + 1)
+        .sum();
+    let position = (line_start + column.saturating_sub(1)).min(body.len());
+
+    let start = position.saturating_sub(EXCERPT_RADIUS);
+    let end = (position + EXCERPT_RADIUS).min(body.len());
+
+    String::from_utf8_lossy(&body[start..end]).into_owned()
+}
@@ -1,3 +1,7 @@
+use crate::decode::DecodeError;
+use crate::id::CoinId;
+use chrono::{DateTime, Utc};
+
 #[derive(Debug)]
 /// Error enum for handling different types of errors within the client
 pub enum Error {
@@ -44,6 +48,28 @@ pub enum Error {
 
     /// Error from JSON creation/processing.
     Json(serde_json::Error),
+
+    /// Response could not be decoded into the expected model.
+    Decode(Box<DecodeError>),
+
+    /// Request parameters failed client-side validation. The request was not sent.
+    Validation {
+        /// Name of the invalid parameter
+        field: &'static str,
+
+        /// Why the parameter value is invalid
+        reason: String,
+    },
+
+    /// The API has no historical price of a coin around a time, e.g. before its first data.
+    MissingPrice {
+        coin_id: CoinId,
+
+        /// Currency the price was looked up in
+        currency_id: CoinId,
+
+        time: DateTime<Utc>,
+    },
 }
 
 impl From<reqwest::Error> for Error {
@@ -69,6 +95,7 @@ impl std::error::Error for Error {
         match *self {
             Error::Reqwest(ref e) => Some(e),
             Error::Json(ref e) => Some(e),
+            Error::Decode(ref e) => Some(e.as_ref()),
             _ => None,
         }
     }
@@ -110,6 +137,23 @@ impl std::fmt::Display for Error {
             Error::Json(err) => {
                 write!(f, "{}", err)
             }
+            Error::Decode(err) => {
+                write!(f, "{}", err)
+            }
+            Error::Validation { field, reason } => {
+                write!(f, "Invalid `{}` parameter: {}", field, reason)
+            }
+            Error::MissingPrice {
+                coin_id,
+                currency_id,
+                time,
+            } => {
+                write!(
+                    f,
+                    "No historical price of `{}` in `{}` at {}.",
+                    coin_id, currency_id, time
+                )
+            }
         }
     }
 }
@@ -1,20 +1,49 @@
 use crate::client::{Client, Response};
+use crate::currency::{quotes_query, QuoteCurrency};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
+use crate::decode::{lenient_list, LenientList};
 use crate::error::Error;
+use crate::id::{CoinId, ExchangeId};
+use crate::number::{deserialize_option_amount, Amount, Price};
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
+
+string_enum! {
+    /// Category of a market
+    pub enum MarketCategory {
+        Spot => "Spot",
+        Derivatives => "Derivatives",
+        Otc => "OTC",
+    }
+}
+
+string_enum! {
+    /// How fees are charged on a market
+    pub enum FeeType {
+        Percentage => "Percentage",
+        NoFees => "No Fees",
+        TransactionMining => "Transaction Mining",
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Fiat currency
 pub struct Fiat {
     pub name: String,
     pub symbol: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Information about given exchange
 pub struct Exchange {
-    pub id: String,
+    pub id: ExchangeId,
     pub name: String,
     pub active: bool,
     pub website_status: bool,
@@ -28,32 +57,79 @@ pub struct Exchange {
     pub currencies: i32,
     pub markets: i32,
     pub fiats: Vec<Fiat>,
-    pub quotes: Value,
+    pub quotes: HashMap<QuoteCurrency, ExchangeQuote>,
     pub last_updated: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+/// Volumes of a given exchange in a single quote currency
+pub struct ExchangeQuote {
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub reported_volume_24h: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub adjusted_volume_24h: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub reported_volume_7d: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub adjusted_volume_7d: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub reported_volume_30d: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub adjusted_volume_30d: Option<Amount>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Information about given exchange market
 pub struct ExchangeMarket {
     pub pair: String,
-    pub base_currency_id: String,
+    pub base_currency_id: CoinId,
     pub base_currency_name: String,
-    pub quote_currency_id: String,
+    pub quote_currency_id: CoinId,
     pub quote_currency_name: String,
     pub market_url: String,
-    pub category: String,
-    pub fee_type: String,
+    pub category: MarketCategory,
+    pub fee_type: FeeType,
     pub outlier: bool,
     pub reported_volume_24h_share: f64,
-    pub quotes: Value,
+    pub quotes: HashMap<QuoteCurrency, MarketQuote>,
     pub last_updated: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+/// Price and volume of a market in a single quote currency
+pub struct MarketQuote {
+    pub price: Price,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub volume_24h: Option<Amount>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Request for getting basic information about exchanges on coinpaprika.com
 /// [/exchanges](https://api.coinpaprika.com/#tag/Exchanges/operation/getExchanges)
 pub struct GetExchangesRequest<'a> {
     client: &'a Client,
-    quotes: Vec<String>,
+    quotes: Vec<QuoteCurrency>,
 }
 
 impl<'a> GetExchangesRequest<'a> {
@@ -64,32 +140,40 @@ impl<'a> GetExchangesRequest<'a> {
         }
     }
 
-    /// List of quotes to return. Up to 3 quotes at once. Currently allowed values:
-    /// BTC, ETH, USD, EUR, PLN, KRW, GBP, CAD, JPY, RUB, TRY, NZD, AUD, CHF, UAH, HKD, SGD, NGN,
-    /// PHP, MXN, BRL, THB, CLP, CNY, CZK, DKK, HUF, IDR, ILS, INR, MYR, NOK, PKR, SEK, TWD, ZAR,
-    /// VND, BOB, COP, PEN, ARS, ISK
-    pub fn quotes(&mut self, quotes: Vec<&str>) -> &'a mut GetExchangesRequest {
-        self.quotes = quotes.iter().map(|&q| String::from(q)).collect();
+    /// List of quotes to return. Up to 3 quotes at once, see [`QuoteCurrency`] for currently
+    /// allowed values.
+    pub fn quotes<Q: Into<QuoteCurrency>>(
+        &mut self,
+        quotes: Vec<Q>,
+    ) -> &'a mut GetExchangesRequest<'_> {
+        self.quotes = quotes.into_iter().map(Into::into).collect();
         self
     }
 
     pub async fn send(&self) -> Result<Vec<Exchange>, Error> {
-        let query = match self.quotes.len() {
-            0 => vec![],
-            _ => vec![("quotes", self.quotes.join(","))],
-        };
+        let response: Response = self.client.request(self.request()?).await?;
 
-        let request: RequestBuilder = self
-            .client
-            .client
-            .get(format!("{}/exchanges", self.client.api_url))
-            .query(&query);
+        let data: Vec<Exchange> = response.json().await?;
 
-        let response: Response = self.client.request(request).await?;
+        Ok(data)
+    }
 
-        let data: Vec<Exchange> = response.response.json().await?;
+    /// Like [`send`](Self::send), but decodes the response item by item. Items that fail to decode
+    /// are skipped and reported in [`LenientList::diagnostics`].
+    pub async fn send_lenient(&self) -> Result<LenientList<Exchange>, Error> {
+        let response: Response = self.client.request(self.request()?).await?;
 
-        Ok(data)
+        lenient_list(response).await
+    }
+
+    fn request(&self) -> Result<RequestBuilder, Error> {
+        let query = quotes_query(&self.quotes)?;
+
+        Ok(self
+            .client
+            .client
+            .get(format!("{}/exchanges", self.client.api_url))
+            .query(&query))
     }
 }
 
@@ -97,46 +181,45 @@ impl<'a> GetExchangesRequest<'a> {
 /// [/exchanges/{exchange_id}](https://api.coinpaprika.com/#tag/Exchanges/operation/getExchangeByID)
 pub struct GetExchangeRequest<'a> {
     client: &'a Client,
-    exchange_id: String,
-    quotes: Vec<String>,
+    exchange_id: ExchangeId,
+    quotes: Vec<QuoteCurrency>,
 }
 
 impl<'a> GetExchangeRequest<'a> {
-    pub fn new(client: &'a Client, exchange_id: &str) -> Self {
+    pub fn new(client: &'a Client, exchange_id: impl Into<ExchangeId>) -> Self {
         Self {
             client,
-            exchange_id: String::from(exchange_id),
+            exchange_id: exchange_id.into(),
             quotes: vec![],
         }
     }
 
-    /// List of quotes to return. Up to 3 quotes at once. Currently allowed values:
-    /// BTC, ETH, USD, EUR, PLN, KRW, GBP, CAD, JPY, RUB, TRY, NZD, AUD, CHF, UAH, HKD, SGD, NGN,
-    /// PHP, MXN, BRL, THB, CLP, CNY, CZK, DKK, HUF, IDR, ILS, INR, MYR, NOK, PKR, SEK, TWD, ZAR,
-    /// VND, BOB, COP, PEN, ARS, ISK
-    pub fn quotes(&mut self, quotes: Vec<&str>) -> &'a mut GetExchangeRequest {
-        self.quotes = quotes.iter().map(|&q| String::from(q)).collect();
+    /// List of quotes to return. Up to 3 quotes at once, see [`QuoteCurrency`] for currently
+    /// allowed values.
+    pub fn quotes<Q: Into<QuoteCurrency>>(
+        &mut self,
+        quotes: Vec<Q>,
+    ) -> &'a mut GetExchangeRequest<'_> {
+        self.quotes = quotes.into_iter().map(Into::into).collect();
         self
     }
 
     pub async fn send(&self) -> Result<Exchange, Error> {
-        let query = match self.quotes.len() {
-            0 => vec![],
-            _ => vec![("quotes", self.quotes.join(","))],
-        };
+        let query = quotes_query(&self.quotes)?;
 
         let request: RequestBuilder = self
             .client
             .client
             .get(format!(
                 "{}/exchanges/{}",
-                self.client.api_url, self.exchange_id
+                self.client.api_url,
+                self.exchange_id.encoded()
             ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Exchange = response.response.json().await?;
+        let data: Exchange = response.json().await?;
 
         Ok(data)
     }
@@ -146,46 +229,45 @@ impl<'a> GetExchangeRequest<'a> {
 /// [/exchanges/{exchange_id}/markets](https://api.coinpaprika.com/#tag/Exchanges/paths/~1exchanges~1%7Bexchange_id%7D~1markets/get)
 pub struct GetExchangeMarketsRequest<'a> {
     client: &'a Client,
-    exchange_id: String,
-    quotes: Vec<String>,
+    exchange_id: ExchangeId,
+    quotes: Vec<QuoteCurrency>,
 }
 
 impl<'a> GetExchangeMarketsRequest<'a> {
-    pub fn new(client: &'a Client, exchange_id: &str) -> Self {
+    pub fn new(client: &'a Client, exchange_id: impl Into<ExchangeId>) -> Self {
         Self {
             client,
-            exchange_id: String::from(exchange_id),
+            exchange_id: exchange_id.into(),
             quotes: vec![],
         }
     }
 
-    /// List of quotes to return. Up to 3 quotes at once. Currently allowed values:
-    /// BTC, ETH, USD, EUR, PLN, KRW, GBP, CAD, JPY, RUB, TRY, NZD, AUD, CHF, UAH, HKD, SGD, NGN,
-    /// PHP, MXN, BRL, THB, CLP, CNY, CZK, DKK, HUF, IDR, ILS, INR, MYR, NOK, PKR, SEK, TWD, ZAR,
-    /// VND, BOB, COP, PEN, ARS, ISK
-    pub fn quotes(&mut self, quotes: Vec<&str>) -> &'a mut GetExchangeMarketsRequest {
-        self.quotes = quotes.iter().map(|&q| String::from(q)).collect();
+    /// List of quotes to return. Up to 3 quotes at once, see [`QuoteCurrency`] for currently
+    /// allowed values.
+    pub fn quotes<Q: Into<QuoteCurrency>>(
+        &mut self,
+        quotes: Vec<Q>,
+    ) -> &'a mut GetExchangeMarketsRequest<'_> {
+        self.quotes = quotes.into_iter().map(Into::into).collect();
         self
     }
 
     pub async fn send(&self) -> Result<Vec<ExchangeMarket>, Error> {
-        let query = match self.quotes.len() {
-            0 => vec![],
-            _ => vec![("quotes", self.quotes.join(","))],
-        };
+        let query = quotes_query(&self.quotes)?;
 
         let request: RequestBuilder = self
             .client
             .client
             .get(format!(
                 "{}/exchanges/{}/markets",
-                self.client.api_url, self.exchange_id
+                self.client.api_url,
+                self.exchange_id.encoded()
             ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<ExchangeMarket> = response.response.json().await?;
+        let data: Vec<ExchangeMarket> = response.json().await?;
 
         Ok(data)
     }
@@ -1,5 +1,8 @@
 use crate::client::{Client, Response};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
 use crate::error::Error;
+use crate::number::{deserialize_amount, Amount};
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
@@ -7,10 +10,12 @@ use serde::{Deserialize, Serialize};
 /// Global market overview data
 pub struct Global {
     /// Total market capitalization - sum of all cryptocurrency market capitalizations in USD
-    pub market_cap_usd: i64,
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub market_cap_usd: Amount,
 
     /// Total 24h volume - sum of all cryptocurrency volumes in USD
-    pub volume_24h_usd: i64,
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub volume_24h_usd: Amount,
 
     /// Bitcoin market capitalization as a percentage of total market capitalization
     pub bitcoin_dominance_percentage: f64,
@@ -22,13 +27,15 @@ pub struct Global {
 
     /// ATH (All Time High) value of market capitalization - the highest historical value of
     /// marketcap
-    pub market_cap_ath_value: i64,
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub market_cap_ath_value: Amount,
 
     /// ATH (All Time High) date of market capitalization
     pub market_cap_ath_date: String,
 
     /// ATH (All Time High) value of the 24h volume - the highest historical value of 24h volume
-    pub volume_24h_ath_value: i64,
+    #[serde(deserialize_with = "deserialize_amount")]
+    pub volume_24h_ath_value: Amount,
 
     /// ATH (All Time High) date of volume 24h
     pub volume_24h_ath_date: String,
@@ -41,6 +48,10 @@ pub struct Global {
 
     /// Timestamp of the last data update
     pub last_updated: i64,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Request for getting global market overview data
@@ -62,7 +73,7 @@ impl<'a> GetGlobalRequest<'a> {
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Global = response.response.json().await?;
+        let data: Global = response.json().await?;
 
         Ok(data)
     }
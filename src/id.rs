@@ -0,0 +1,140 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use serde::{Deserialize, Serialize};
+
+/// Characters escaped when an identifier is used as a URL path segment
+static PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Escapes `value` for use as a URL path segment.
+pub(crate) fn encode_path_segment(value: &str) -> String {
+    utf8_percent_encode(value, PATH_SEGMENT).to_string()
+}
+
+/// Defines a newtype wrapping a coinpaprika.com identifier.
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
+
+            /// Returns the identifier as used by the API
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Returns the identifier escaped for use as a URL path segment
+            pub fn encoded(&self) -> String {
+                encode_path_segment(&self.0)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(String::from(id))
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(id: &$name) -> Self {
+                id.clone()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> String {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+
+            fn from_str(id: &str) -> Result<Self, Self::Err> {
+                Ok(Self::from(id))
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+id_type! {
+    /// ID of a coin on coinpaprika.com, e.g. `btc-bitcoin`
+    CoinId
+}
+
+id_type! {
+    /// ID of an exchange on coinpaprika.com, e.g. `binance`
+    ExchangeId
+}
+
+id_type! {
+    /// ID of a tag on coinpaprika.com, e.g. `blockchain-service`
+    TagId
+}
+
+id_type! {
+    /// ID of a person on coinpaprika.com, e.g. `vitalik-buterin`
+    PersonId
+}
+
+id_type! {
+    /// ID of a contract platform on coinpaprika.com, e.g. `eth-ethereum`
+    PlatformId
+}
+
+impl CoinId {
+    /// Splits the identifier into the lowercase symbol and the name slug, e.g. `btc-bitcoin` into
+    /// `("btc", "bitcoin")`. Returns `None` if the identifier has no `-` separator.
+    pub fn parts(&self) -> Option<(&str, &str)> {
+        self.0.split_once('-')
+    }
+
+    /// Lowercase symbol part of the identifier, e.g. `btc` for `btc-bitcoin`
+    pub fn symbol(&self) -> &str {
+        self.parts().map_or(self.as_str(), |(symbol, _)| symbol)
+    }
+
+    /// Name slug part of the identifier, e.g. `bitcoin` for `btc-bitcoin`
+    pub fn slug(&self) -> Option<&str> {
+        self.parts().map(|(_, slug)| slug)
+    }
+}
@@ -1,8 +1,25 @@
 use crate::client::{Client, Response};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
 use crate::error::Error;
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
+string_enum! {
+    /// Status of the API plan subscription
+    pub enum PlanStatus {
+        /// The subscription is active
+        Active => "active",
+
+        /// The subscription payment failed. If payment is not made within 7 days, then the
+        /// subscription will expire
+        PastDue => "past_due",
+
+        /// The subscription is inactive
+        Inactive => "inactive",
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 /// Requests made and left stats
 pub struct CurrentMonthUsage {
@@ -13,6 +30,10 @@ pub struct CurrentMonthUsage {
     /// Number of requests left in the current month. If a plan has no limit on the number of
     /// requests, e.g., `Enterprise` plan, then the value of `requests_left` is -1
     pub requests_left: i32,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,6 +45,10 @@ pub struct KeyUsage {
 
     /// Requests made and left stats
     pub current_month: CurrentMonthUsage,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,16 +60,18 @@ pub struct KeyInfo {
     /// A date when the plan started in RFC3999 (ISO-8601) format
     pub plan_started_at: String,
 
-    /// Status of the plan. There are 3 possible statuses: `active` - the subscription is active;
-    /// `past_due` - the subscription payment failed. If payment is not made within 7 days, then
-    /// the subscription will expire; `inactive` - the subscription is inactive.
-    pub plan_status: String,
+    /// Status of the plan
+    pub plan_status: PlanStatus,
 
     /// API Customer Portal URL
     pub portal_url: String,
 
     /// Monthly usage for the API key
     pub usage: KeyUsage,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Request for getting API key information
@@ -66,7 +93,7 @@ impl<'a> GetKeyInfoRequest<'a> {
 
         let response: Response = self.client.request(request).await?;
 
-        let data: KeyInfo = response.response.json().await?;
+        let data: KeyInfo = response.json().await?;
 
         Ok(data)
     }
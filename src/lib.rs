@@ -18,7 +18,7 @@
 //!
 //! Then you can use it like this:
 //!
-//! ```rust
+//! ```rust,no_run
 //! use coinpaprika_api::client::Client;
 //! use coinpaprika_api::global::Global;
 //! use std::error::Error;
@@ -39,7 +39,7 @@
 //! If you have an API key, `Client` struct has an additional constructor
 //! `with_key`, that takes API key as an argument:
 //!
-//! ```rust
+//! ```rust,no_run
 //! use coinpaprika_api::client::Client;
 //! use coinpaprika_api::global::Global;
 //! use std::error::Error;
@@ -56,6 +56,20 @@
 //! ```
 //!
 //!
+//! ## Features
+//!
+//! - `decimal` - deserialize prices, volumes, market caps and conversion amounts into
+//!   `rust_decimal::Decimal` instead of `f64`. JSON numbers are still parsed as `f64` first, so
+//!   they are rounded to about 17 significant digits before becoming decimals. Only values sent
+//!   as strings keep all of their digits. See the [`number`] module for the affected types.
+//! - `extra-fields` - keep fields not declared in response models in their
+//!   [`extra`](decode::Extra) map, so they are not lost when a model is re-serialized. Declared
+//!   fields are re-serialized from their parsed values, e.g. numeric strings become numbers, so
+//!   the output is not identical to the response. With this feature enabled
+//!   [`Error::Decode`](error::Error::Decode) reports the path of the failing model rather than of
+//!   the failing field.
+//!
+//!
 //! ## Supported Endpoints
 //!
 //! - Key
@@ -99,8 +113,8 @@
 //! - Contracts
 //!     - [x] List contracts platforms
 //!     - [x] Get all contract addressess for a given platform
-//!     - [ ] Redirect to Ticker by contract address
-//!     - [ ] Redirect to historical ticks by contract address
+//!     - [x] Redirect to Ticker by contract address
+//!     - [x] Redirect to historical ticks by contract address
 //!
 //! - Changelog
 //!     - [x] Get id changelog for all coins
@@ -116,12 +130,31 @@
 //! CoinpaprikaAPI Rust client is available under the MIT license. See the [LICENSE file](./LICENSE) for more info.
 //!
 
+#[macro_use]
+mod macros;
+
 /// Client struct used for connecting with coinpaprika.com
 pub mod client;
 
 /// Possible errors Client can return
 pub mod error;
 
+/// Numeric types used for prices, volumes and amounts
+pub mod number;
+
+/// Decoding of API responses
+pub mod decode;
+
+/// Quote currencies supported by the API
+pub mod currency;
+
+/// Identifiers of coins, exchanges, tags, people and contract platforms
+pub mod id;
+
+mod validate;
+
+mod window;
+
 //
 // API Sections
 //
@@ -155,3 +188,16 @@ pub mod contracts;
 
 /// Requests for "Changelog" section of the API
 pub mod changelog;
+
+//
+// Analytics
+//
+
+/// Computations over historical data returned by the API
+pub mod analytics;
+
+/// Historical prices at arbitrary timestamps
+pub mod pricing;
+
+/// Valuation of coin holdings
+pub mod portfolio;
@@ -0,0 +1,190 @@
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+
+use serde::de::{self, Deserializer, Visitor};
+use std::fmt;
+
+#[cfg(not(feature = "decimal"))]
+/// Type used for prices and other per-unit monetary values.
+///
+/// `f64` by default, `rust_decimal::Decimal` with the `decimal` feature enabled. Even then, values
+/// sent as JSON numbers are parsed as `f64` first and keep only about 17 significant digits.
+pub type Price = f64;
+
+#[cfg(feature = "decimal")]
+/// Type used for prices and other per-unit monetary values.
+///
+/// `f64` by default, [`Decimal`] with the `decimal` feature enabled. Even then, values sent as
+/// JSON numbers are parsed as `f64` first and keep only about 17 significant digits.
+pub type Price = Decimal;
+
+#[cfg(not(feature = "decimal"))]
+/// Type used for supplies, volumes, market capitalizations and conversion amounts.
+///
+/// `f64` by default, `rust_decimal::Decimal` with the `decimal` feature enabled. Even then, values
+/// sent as JSON numbers are parsed as `f64` first and keep only about 17 significant digits, while
+/// numeric strings keep all of theirs. With the `decimal` feature, optional values outside the
+/// range of `Decimal` (about ±7.9e28) deserialize as `None` and required ones fail.
+pub type Amount = f64;
+
+#[cfg(feature = "decimal")]
+/// Type used for supplies, volumes, market capitalizations and conversion amounts.
+///
+/// `f64` by default, [`Decimal`] with the `decimal` feature enabled. Even then, values sent as JSON
+/// numbers are parsed as `f64` first and keep only about 17 significant digits, while numeric
+/// strings keep all of theirs. With the `decimal` feature, optional values outside the range of
+/// `Decimal` (about ±7.9e28) deserialize as `None` and required ones fail.
+pub type Amount = Decimal;
+
+/// Deserializes an [`Amount`] from a JSON number of any size or from a numeric string.
+pub(crate) fn deserialize_amount<'de, D>(deserializer: D) -> Result<Amount, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let visitor = AmountVisitor { optional: false };
+
+    deserializer
+        .deserialize_any(visitor)?
+        .ok_or_else(|| de::Error::invalid_type(de::Unexpected::Unit, &visitor))
+}
+
+/// Deserializes an optional [`Amount`] from a JSON number of any size, a numeric string, an empty
+/// string or `null`. Numbers that do not fit in an [`Amount`] become `None`.
+pub(crate) fn deserialize_option_amount<'de, D>(deserializer: D) -> Result<Option<Amount>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(AmountVisitor { optional: true })
+}
+
+#[derive(Clone, Copy)]
+struct AmountVisitor {
+    /// Whether numbers out of range are `None` rather than an error
+    optional: bool,
+}
+
+impl AmountVisitor {
+    fn out_of_range<E: de::Error>(self, unexpected: de::Unexpected) -> Result<Option<Amount>, E> {
+        match self.optional {
+            true => Ok(None),
+            false => Err(E::invalid_value(unexpected, &self)),
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for AmountVisitor {
+    type Value = Option<Amount>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a number, a numeric string or null")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        self.visit_i128(v.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        self.visit_u128(v.into())
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        match amount_from_i128(v) {
+            Some(amount) => Ok(Some(amount)),
+            None => self.out_of_range(de::Unexpected::Other("out of range integer")),
+        }
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        match amount_from_u128(v) {
+            Some(amount) => Ok(Some(amount)),
+            None => self.out_of_range(de::Unexpected::Other("out of range integer")),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        match amount_from_f64(v) {
+            Some(amount) => Ok(Some(amount)),
+            None => self.out_of_range(de::Unexpected::Float(v)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let v = v.trim();
+
+        if v.is_empty() {
+            return Ok(None);
+        }
+
+        match amount_from_str(v) {
+            Some(amount) => Ok(Some(amount)),
+            None if v.parse::<f64>().is_ok() => self.out_of_range(de::Unexpected::Str(v)),
+            None => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+        }
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+#[cfg(not(feature = "decimal"))]
+fn amount_from_i128(v: i128) -> Option<Amount> {
+    Some(v as f64)
+}
+
+#[cfg(not(feature = "decimal"))]
+fn amount_from_u128(v: u128) -> Option<Amount> {
+    Some(v as f64)
+}
+
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn amount_from_f64(v: f64) -> Option<Amount> {
+    v.is_finite().then_some(v)
+}
+
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn amount_to_f64(v: Amount) -> f64 {
+    v
+}
+
+#[cfg(not(feature = "decimal"))]
+fn amount_from_str(v: &str) -> Option<Amount> {
+    v.parse::<f64>().ok().and_then(amount_from_f64)
+}
+
+#[cfg(feature = "decimal")]
+fn amount_from_i128(v: i128) -> Option<Amount> {
+    Decimal::try_from_i128_with_scale(v, 0).ok()
+}
+
+#[cfg(feature = "decimal")]
+fn amount_from_u128(v: u128) -> Option<Amount> {
+    i128::try_from(v).ok().and_then(amount_from_i128)
+}
+
+#[cfg(feature = "decimal")]
+/// `None` for values outside the range of [`Decimal`] (about ±7.9e28) and for non-finite values.
+/// Digits beyond the precision of `f64` are rounded, e.g. `0.1` stays `0.1`.
+pub(crate) fn amount_from_f64(v: f64) -> Option<Amount> {
+    Decimal::try_from(v).ok()
+}
+
+#[cfg(feature = "decimal")]
+pub(crate) fn amount_to_f64(v: Amount) -> f64 {
+    use rust_decimal::prelude::ToPrimitive;
+
+    v.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg(feature = "decimal")]
+fn amount_from_str(v: &str) -> Option<Amount> {
+    v.parse().ok().or_else(|| Decimal::from_scientific(v).ok())
+}
@@ -1,5 +1,8 @@
 use crate::client::{Client, Response};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
 use crate::error::Error;
+use crate::id::{CoinId, PersonId};
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -7,16 +10,20 @@ use serde_json::Value;
 #[derive(Debug, Serialize, Deserialize)]
 /// Position of a person
 pub struct Position {
-    pub coin_id: String,
+    pub coin_id: CoinId,
     pub coin_name: String,
     pub position: String,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Information about a person
 pub struct Person {
     /// ID of person
-    pub id: String,
+    pub id: PersonId,
 
     /// Name of person
     pub name: String,
@@ -32,6 +39,10 @@ pub struct Person {
 
     /// Positions the person holds in various projects
     pub positions: Vec<Position>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Request for getting information about a person with the specified ID, related to the
@@ -40,26 +51,27 @@ pub struct Person {
 /// [/people/{person_id}](https://api.coinpaprika.com/#tag/People/operation/getPeopleById)
 pub struct GetPersonRequest<'a> {
     client: &'a Client,
-    person_id: String,
+    person_id: PersonId,
 }
 
 impl<'a> GetPersonRequest<'a> {
-    pub fn new(client: &'a Client, person_id: &str) -> Self {
+    pub fn new(client: &'a Client, person_id: impl Into<PersonId>) -> Self {
         Self {
             client,
-            person_id: String::from(person_id),
+            person_id: person_id.into(),
         }
     }
 
     pub async fn send(&self) -> Result<Person, Error> {
-        let request: RequestBuilder = self
-            .client
-            .client
-            .get(format!("{}/people/{}", self.client.api_url, self.person_id));
+        let request: RequestBuilder = self.client.client.get(format!(
+            "{}/people/{}",
+            self.client.api_url,
+            self.person_id.encoded()
+        ));
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Person = response.response.json().await?;
+        let data: Person = response.json().await?;
 
         Ok(data)
     }
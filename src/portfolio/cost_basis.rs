@@ -0,0 +1,599 @@
+use crate::client::Client;
+use crate::error::Error;
+use crate::id::CoinId;
+use crate::number::{amount_from_f64, Amount, Price};
+use crate::pricing::USD_ID;
+use crate::tickers::ApiPlan;
+use chrono::{DateTime, Datelike, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use futures::TryFutureExt;
+use std::collections::{BTreeMap, HashMap};
+use std::future;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Order in which lots are consumed by disposals
+pub enum LotMethod {
+    /// First in, first out: oldest lots first
+    Fifo,
+
+    /// Last in, first out: newest lots first
+    Lifo,
+
+    /// Highest in, first out: lots with the highest unit cost first
+    Hifo,
+
+    /// Open lots of a coin share the average unit cost of the pool at each disposal, then are
+    /// consumed oldest first
+    AverageCost,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Kind of a [`LedgerEntry`]
+pub enum EntryKind {
+    /// Acquisition opening a lot at the fiat value paid
+    Buy,
+
+    /// Disposal realizing a gain or loss against the fiat value received
+    Sell,
+
+    /// Coins received from outside the ledger. They first take back the lots of the same coin sent
+    /// by earlier transfers out, with their acquisition date and cost basis, as when moving coins
+    /// between own wallets. Any amount beyond those opens a lot at its fiat value at that time.
+    TransferIn,
+
+    /// Coins sent outside the ledger, closing lots without realizing any gain. The closed lots are
+    /// kept for later transfers in of the same coin.
+    TransferOut,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Dated buy, sell or transfer of a coin
+pub struct LedgerEntry {
+    pub time: DateTime<Utc>,
+    pub coin_id: CoinId,
+    pub kind: EntryKind,
+
+    /// Amount of the coin, always positive
+    pub amount: Amount,
+
+    /// Fiat value of the whole `amount`, paid or received. `None` to use the historical price at
+    /// `time`.
+    pub value: Option<Amount>,
+}
+
+impl LedgerEntry {
+    pub fn new(
+        time: DateTime<Utc>,
+        coin_id: impl Into<CoinId>,
+        kind: EntryKind,
+        amount: impl Into<Amount>,
+    ) -> Self {
+        Self {
+            time,
+            coin_id: coin_id.into(),
+            kind,
+            amount: amount.into(),
+            value: None,
+        }
+    }
+
+    /// Sets the fiat value of the whole amount
+    pub fn value(mut self, value: impl Into<Amount>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Change of the amount held
+    fn delta(&self) -> Amount {
+        match self.kind {
+            EntryKind::Buy | EntryKind::TransferIn => self.amount,
+            EntryKind::Sell | EntryKind::TransferOut => Amount::default() - self.amount,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Coins acquired together, with what is left of them
+pub struct Lot {
+    pub coin_id: CoinId,
+    pub acquired: DateTime<Utc>,
+
+    /// Amount acquired
+    pub amount: Amount,
+
+    /// Amount not disposed of yet
+    pub remaining: Amount,
+
+    /// Fiat cost basis of `remaining`
+    pub cost: Amount,
+}
+
+impl Lot {
+    /// Cost basis of one unit
+    pub fn unit_cost(&self) -> Price {
+        match self.remaining == Amount::default() {
+            true => Price::default(),
+            false => self.cost / self.remaining,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Gain or loss realized by selling part of a lot
+pub struct RealizedGain {
+    pub coin_id: CoinId,
+    pub acquired: DateTime<Utc>,
+    pub disposed: DateTime<Utc>,
+    pub amount: Amount,
+    pub cost: Amount,
+    pub proceeds: Amount,
+
+    /// `proceeds - cost`, negative for a loss
+    pub gain: Amount,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Gain or loss of an open lot at the current price
+pub struct UnrealizedGain {
+    pub lot: Lot,
+
+    /// Current price of the coin, `None` if unknown
+    pub price: Option<Price>,
+
+    /// Current value of the remaining amount
+    pub value: Option<Amount>,
+
+    /// `value - cost`, negative for a loss
+    pub gain: Option<Amount>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Gains of a calendar year (UTC)
+pub struct YearGains {
+    pub year: i32,
+
+    /// Proceeds of the sales of the year
+    pub proceeds: Amount,
+
+    /// Cost basis of the sales of the year
+    pub cost: Amount,
+
+    /// Gains realized by the sales of the year
+    pub realized: Amount,
+
+    /// Unrealized gains of the open lots acquired during the year, valued at the current price of
+    /// their coin rather than at the end of the year. Lots whose current price is unknown are left
+    /// out.
+    pub unrealized: Amount,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Lot accounting of a ledger
+pub struct CostBasis {
+    pub method: LotMethod,
+
+    /// Realized gains per lot, in order of disposal
+    pub realized: Vec<RealizedGain>,
+
+    /// Open lots with their unrealized gains, in order of acquisition
+    pub unrealized: Vec<UnrealizedGain>,
+
+    /// Gains per year, in ascending order
+    pub years: Vec<YearGains>,
+}
+
+impl CostBasis {
+    /// Accounts for `ledger` with `method`, valuing open lots with the current `prices` of their
+    /// coin.
+    ///
+    /// Every entry needs a `value`, except transfers out and transfers in covered by earlier
+    /// transfers out of the same coin. Entries are processed in order of `time`, keeping the
+    /// ledger order for entries at the same time. Fails when an entry has no value or disposes of
+    /// more than what is held.
+    pub fn new(
+        ledger: &[LedgerEntry],
+        method: LotMethod,
+        prices: &HashMap<CoinId, Price>,
+    ) -> Result<Self, Error> {
+        let mut entries: Vec<&LedgerEntry> = ledger.iter().collect();
+        entries.sort_by_key(|entry| entry.time);
+
+        let mut lots: Vec<Lot> = vec![];
+        let mut in_transit: Vec<Lot> = vec![];
+        let mut realized = vec![];
+
+        for entry in entries {
+            match entry.kind {
+                EntryKind::Buy => lots.push(Lot {
+                    coin_id: entry.coin_id.clone(),
+                    acquired: entry.time,
+                    amount: entry.amount,
+                    remaining: entry.amount,
+                    cost: entry_value(entry)?,
+                }),
+                EntryKind::TransferIn => receive(&mut lots, &mut in_transit, entry)?,
+                EntryKind::Sell => realized.extend(dispose(&mut lots, entry, method)?),
+                EntryKind::TransferOut => {
+                    in_transit.extend(dispose(&mut lots, entry, method)?.into_iter().map(|gain| {
+                        Lot {
+                            coin_id: gain.coin_id,
+                            acquired: gain.acquired,
+                            amount: gain.amount,
+                            remaining: gain.amount,
+                            cost: gain.cost,
+                        }
+                    }))
+                }
+            }
+        }
+
+        let unrealized: Vec<UnrealizedGain> = lots
+            .into_iter()
+            .map(|lot| {
+                let price = prices.get(&lot.coin_id).copied();
+                let value = price.map(|price| lot.remaining * price);
+
+                UnrealizedGain {
+                    gain: value.map(|value| value - lot.cost),
+                    lot,
+                    price,
+                    value,
+                }
+            })
+            .collect();
+
+        let mut years: BTreeMap<i32, YearGains> = BTreeMap::new();
+
+        for gain in &realized {
+            let year = years.entry(gain.disposed.year()).or_default();
+            year.proceeds += gain.proceeds;
+            year.cost += gain.cost;
+            year.realized += gain.gain;
+        }
+
+        for gain in &unrealized {
+            let year = years.entry(gain.lot.acquired.year()).or_default();
+            year.unrealized += gain.gain.unwrap_or_default();
+        }
+
+        Ok(Self {
+            method,
+            realized,
+            unrealized,
+            years: years
+                .into_iter()
+                .map(|(year, gains)| YearGains { year, ..gains })
+                .collect(),
+        })
+    }
+
+    /// Sum of realized gains
+    pub fn realized_total(&self) -> Amount {
+        self.realized.iter().map(|gain| gain.gain).sum()
+    }
+
+    /// Sum of unrealized gains of the lots whose current price is known
+    pub fn unrealized_total(&self) -> Amount {
+        self.unrealized.iter().filter_map(|gain| gain.gain).sum()
+    }
+}
+
+fn entry_value(entry: &LedgerEntry) -> Result<Amount, Error> {
+    entry.value.ok_or_else(|| Error::Validation {
+        field: "ledger",
+        reason: format!(
+            "no value for {} {} at {}",
+            entry.amount, entry.coin_id, entry.time
+        ),
+    })
+}
+
+/// Tolerates rounding errors of floating point amounts
+fn dust(amount: Amount) -> Amount {
+    amount_from_f64(1e-9).unwrap_or_default() * amount
+}
+
+/// Amount of the transfer in `entry` not covered by the coins in transit, taking those back
+fn reclaim(in_transit: &mut Vec<Lot>, entry: &LedgerEntry) -> (Vec<Lot>, Amount) {
+    let mut left = entry.amount;
+    let mut reclaimed = vec![];
+
+    for lot in in_transit.iter_mut() {
+        if left <= Amount::default() {
+            break;
+        }
+
+        if lot.coin_id != entry.coin_id {
+            continue;
+        }
+
+        let amount = match lot.remaining < left {
+            true => lot.remaining,
+            false => left,
+        };
+        let cost = lot.cost * amount / lot.remaining;
+
+        lot.cost -= cost;
+        lot.remaining -= amount;
+        left -= amount;
+
+        reclaimed.push(Lot {
+            coin_id: lot.coin_id.clone(),
+            acquired: lot.acquired,
+            amount,
+            remaining: amount,
+            cost,
+        });
+    }
+
+    in_transit.retain(|lot| lot.remaining > Amount::default());
+
+    (reclaimed, left)
+}
+
+/// Adds the transfer in `entry` to the open lots, first as the coins in transit it covers
+fn receive(
+    lots: &mut Vec<Lot>,
+    in_transit: &mut Vec<Lot>,
+    entry: &LedgerEntry,
+) -> Result<(), Error> {
+    let (reclaimed, left) = reclaim(in_transit, entry);
+    lots.extend(reclaimed);
+
+    if left > dust(entry.amount) {
+        lots.push(Lot {
+            coin_id: entry.coin_id.clone(),
+            acquired: entry.time,
+            amount: left,
+            remaining: left,
+            cost: entry_value(entry)? * left / entry.amount,
+        });
+    }
+
+    // Keeps the lots of a coin in order of acquisition, as disposals expect.
+    lots.sort_by_key(|lot| lot.acquired);
+
+    Ok(())
+}
+
+/// Removes `entry.amount` from the open lots of its coin, returning the parts of lots closed,
+/// with no proceeds for a transfer out
+fn dispose(
+    lots: &mut Vec<Lot>,
+    entry: &LedgerEntry,
+    method: LotMethod,
+) -> Result<Vec<RealizedGain>, Error> {
+    let proceeds = match entry.kind {
+        EntryKind::Sell => entry_value(entry)?,
+        _ => Amount::default(),
+    };
+
+    let mut open: Vec<usize> = (0..lots.len())
+        .filter(|i| lots[*i].coin_id == entry.coin_id)
+        .collect();
+
+    match method {
+        LotMethod::Fifo => {}
+        LotMethod::Lifo => open.reverse(),
+        LotMethod::Hifo => open.sort_by(|a, b| {
+            lots[*b]
+                .unit_cost()
+                .partial_cmp(&lots[*a].unit_cost())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }),
+        LotMethod::AverageCost => {
+            let remaining: Amount = open.iter().map(|i| lots[*i].remaining).sum();
+            let cost: Amount = open.iter().map(|i| lots[*i].cost).sum();
+
+            if remaining != Amount::default() {
+                for i in &open {
+                    lots[*i].cost = cost * lots[*i].remaining / remaining;
+                }
+            }
+        }
+    }
+
+    let mut left = entry.amount;
+    let mut realized = vec![];
+
+    for i in open {
+        if left <= Amount::default() {
+            break;
+        }
+
+        let lot = &mut lots[i];
+        let amount = match lot.remaining < left {
+            true => lot.remaining,
+            false => left,
+        };
+        let cost = lot.cost * amount / lot.remaining;
+
+        lot.cost -= cost;
+        lot.remaining -= amount;
+        left -= amount;
+
+        let proceeds = proceeds * amount / entry.amount;
+
+        realized.push(RealizedGain {
+            coin_id: entry.coin_id.clone(),
+            acquired: lot.acquired,
+            disposed: entry.time,
+            amount,
+            cost,
+            proceeds,
+            gain: proceeds - cost,
+        });
+    }
+
+    lots.retain(|lot| lot.remaining > Amount::default());
+
+    if left > dust(entry.amount) {
+        return Err(Error::Validation {
+            field: "ledger",
+            reason: format!(
+                "{} {} at {} is {} more than the amount held",
+                entry.amount, entry.coin_id, entry.time, left
+            ),
+        });
+    }
+
+    Ok(realized)
+}
+
+/// Request for the [`CostBasis`] of a ledger, in US dollars by default.
+///
+/// Entries needing a value without one are valued at the historical price of their coin at their
+/// time, using [`GetConversionAtRequest`](crate::pricing::GetConversionAtRequest), and fail with
+/// [`Error::MissingPrice`] when that price is unknown. Open lots are valued at the current price
+/// from [/price-converter](https://api.coinpaprika.com/#tag/Tools/paths/~1price-converter/get).
+pub struct GetCostBasisRequest<'a> {
+    client: &'a Client,
+    ledger: Vec<LedgerEntry>,
+    method: LotMethod,
+    currency_id: CoinId,
+    plan: Option<ApiPlan>,
+    concurrency: usize,
+}
+
+impl<'a> GetCostBasisRequest<'a> {
+    pub fn new(client: &'a Client, ledger: Vec<LedgerEntry>) -> Self {
+        Self {
+            client,
+            ledger,
+            method: LotMethod::Fifo,
+            currency_id: CoinId::from(USD_ID),
+            plan: None,
+            concurrency: 4,
+        }
+    }
+
+    /// Default: [`LotMethod::Fifo`]
+    pub fn method(&mut self, method: LotMethod) -> &'a mut GetCostBasisRequest<'_> {
+        self.method = method;
+        self
+    }
+
+    /// Currency of values, the US dollar or a coin, as historical prices are only looked up in
+    /// those (see [`GetConversionAtRequest`](crate::pricing::GetConversionAtRequest)). Values
+    /// already set on entries must be in it.
+    ///
+    /// Default: `usd-us-dollars`
+    pub fn currency(&mut self, currency_id: impl Into<CoinId>) -> &'a mut GetCostBasisRequest<'_> {
+        self.currency_id = currency_id.into();
+        self
+    }
+
+    /// API plan of the client, to skip intervals it does not allow when looking up historical
+    /// prices
+    ///
+    /// Default: all intervals are tried
+    pub fn plan(&mut self, plan: ApiPlan) -> &'a mut GetCostBasisRequest<'_> {
+        self.plan = Some(plan);
+        self
+    }
+
+    /// Maximum number of price lookups in flight at once, at least 1
+    ///
+    /// Default: `4`
+    pub fn concurrency(&mut self, concurrency: usize) -> &'a mut GetCostBasisRequest<'_> {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub async fn send(&self) -> Result<CostBasis, Error> {
+        let mut ledger = self.ledger.clone();
+
+        let values: Vec<(usize, Amount)> = stream::iter(self.unvalued())
+            .map(|i| self.value_at(&ledger[i]).map_ok(move |value| (i, value)))
+            .buffered(self.concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        for (i, value) in values {
+            ledger[i].value = Some(value);
+        }
+
+        let mut held: HashMap<&CoinId, Amount> = HashMap::new();
+
+        for entry in &ledger {
+            *held.entry(&entry.coin_id).or_default() += entry.delta();
+        }
+
+        let prices: HashMap<CoinId, Price> = stream::iter(held)
+            .filter(|(_, amount)| future::ready(*amount > Amount::default()))
+            .map(|(coin_id, _)| async move {
+                let conversion = self
+                    .client
+                    .price_convert(coin_id, &self.currency_id)
+                    .amount(1)
+                    .send()
+                    .await?;
+
+                Ok::<_, Error>((coin_id.clone(), conversion.price))
+            })
+            .buffered(self.concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        CostBasis::new(&ledger, self.method, &prices)
+    }
+
+    /// Indices of the entries of the ledger needing a historical value: those without one,
+    /// except transfers out and transfers in covered by earlier transfers out
+    fn unvalued(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.ledger.len()).collect();
+        order.sort_by_key(|i| self.ledger[*i].time);
+
+        let mut in_transit: HashMap<&CoinId, Amount> = HashMap::new();
+        let mut unvalued = vec![];
+
+        for i in order {
+            let entry = &self.ledger[i];
+            let sent = in_transit.entry(&entry.coin_id).or_default();
+
+            let left = match entry.kind {
+                EntryKind::TransferOut => {
+                    *sent += entry.amount;
+                    continue;
+                }
+                EntryKind::TransferIn => {
+                    let covered = match *sent < entry.amount {
+                        true => *sent,
+                        false => entry.amount,
+                    };
+                    *sent -= covered;
+                    entry.amount - covered
+                }
+                EntryKind::Buy | EntryKind::Sell => entry.amount,
+            };
+
+            if entry.value.is_none() && left > dust(entry.amount) {
+                unvalued.push(i);
+            }
+        }
+
+        unvalued.sort();
+        unvalued
+    }
+
+    /// Value of the whole amount of `entry` at its time
+    async fn value_at(&self, entry: &LedgerEntry) -> Result<Amount, Error> {
+        let mut request =
+            self.client
+                .convert_at(&entry.coin_id, &self.currency_id, entry.amount, entry.time);
+
+        let conversion = match self.plan {
+            Some(plan) => request.plan(plan).send().await?,
+            None => request.send().await?,
+        };
+
+        match conversion {
+            Some(conversion) => Ok(conversion.converted),
+            None => Err(Error::MissingPrice {
+                coin_id: entry.coin_id.clone(),
+                currency_id: self.currency_id.clone(),
+                time: entry.time,
+            }),
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use crate::client::Client;
+use crate::error::Error;
+use crate::id::CoinId;
+use crate::number::{Amount, Price};
+use crate::portfolio::Portfolio;
+use crate::pricing::{PriceCache, Samples};
+use crate::tickers::{HistoricalTick, Interval, MAX_HISTORICAL_TICKS};
+use crate::validate::{self, HISTORICAL_QUOTES};
+use crate::window;
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Dated change of the amount held of a coin
+pub struct Transaction {
+    pub time: DateTime<Utc>,
+    pub coin_id: CoinId,
+
+    /// Positive when coins are received, negative when they are sent or sold
+    pub amount: Amount,
+}
+
+impl Transaction {
+    pub fn new(time: DateTime<Utc>, coin_id: impl Into<CoinId>, amount: impl Into<Amount>) -> Self {
+        Self {
+            time,
+            coin_id: coin_id.into(),
+            amount: amount.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Value of a portfolio at a point in time
+pub struct ValuePoint {
+    pub time: DateTime<Utc>,
+
+    /// Value of the priced positions
+    pub value: Amount,
+
+    /// Coins held at that time without any price yet, e.g. before their `first_data_at`, left
+    /// out of `value`
+    pub unpriced: Vec<CoinId>,
+}
+
+impl Portfolio {
+    /// Value of the portfolio at each of `times`, sorted in ascending order.
+    ///
+    /// The positions of the portfolio are the holdings before any of `transactions`, which are
+    /// applied at their `time`. Each coin is priced with its last tick at or before the point.
+    pub fn value_history(
+        &self,
+        transactions: &[Transaction],
+        ticks: &[(CoinId, Vec<HistoricalTick>)],
+        times: &[DateTime<Utc>],
+    ) -> Vec<ValuePoint> {
+        let prices = ticks
+            .iter()
+            .map(|(coin_id, ticks)| {
+                let samples = ticks
+                    .iter()
+                    .filter_map(|tick| Some((tick.datetime()?, tick.price)))
+                    .collect();
+
+                (coin_id.clone(), samples)
+            })
+            .collect();
+
+        self.sampled_history(transactions, prices, times)
+    }
+
+    fn sampled_history(
+        &self,
+        transactions: &[Transaction],
+        mut prices: HashMap<CoinId, Samples>,
+        times: &[DateTime<Utc>],
+    ) -> Vec<ValuePoint> {
+        for samples in prices.values_mut() {
+            samples.sort_by_key(|(time, _)| *time);
+        }
+
+        let mut transactions: Vec<&Transaction> = transactions.iter().collect();
+        transactions.sort_by_key(|transaction| transaction.time);
+        let mut transactions = transactions.into_iter().peekable();
+
+        let mut holdings = self.clone();
+        let mut points = vec![];
+
+        for &time in times {
+            while let Some(transaction) = transactions.next_if(|t| t.time <= time) {
+                holdings.add(transaction.coin_id.clone(), transaction.amount);
+            }
+
+            let mut value = Amount::default();
+            let mut unpriced = vec![];
+
+            for position in holdings.positions() {
+                if position.amount == Amount::default() {
+                    continue;
+                }
+
+                match prices
+                    .get(&position.coin_id)
+                    .and_then(|samples| price_at(samples, time))
+                {
+                    Some(price) => value += position.amount * price,
+                    None => unpriced.push(position.coin_id.clone()),
+                }
+            }
+
+            points.push(ValuePoint {
+                time,
+                value,
+                unpriced,
+            });
+        }
+
+        points
+    }
+}
+
+/// Price of the last sample at or before `time`
+fn price_at(samples: &[(DateTime<Utc>, Price)], time: DateTime<Utc>) -> Option<Price> {
+    let index = samples.partition_point(|(sample, _)| *sample <= time);
+
+    index.checked_sub(1).map(|index| samples[index].1)
+}
+
+/// Multiples of `step` since the Unix epoch between `start` and `end`, both inclusive
+fn grid(start: DateTime<Utc>, end: DateTime<Utc>, step: Duration) -> Vec<DateTime<Utc>> {
+    let step = step.num_seconds();
+    let mut time = start.timestamp().div_euclid(step) * step;
+
+    if time < start.timestamp() {
+        time += step;
+    }
+
+    (time..=end.timestamp())
+        .step_by(step as usize)
+        .filter_map(|time| DateTime::from_timestamp(time, 0))
+        .collect()
+}
+
+/// Request for the value of a [`Portfolio`] over time, from the
+/// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
+/// ticks of each coin.
+///
+/// Ticks of a coin are only requested from its `first_data_at`, looked up with one
+/// [/ticker/{coin_id}](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersById) call
+/// per coin. Coins without a readable ticker are requested from the start. Ticks are fetched in
+/// windows of at most [`MAX_HISTORICAL_TICKS`] points cached in the [`Client`], so requesting the
+/// same range again only fetches windows that had not ended yet.
+pub struct GetPortfolioHistoryRequest<'a> {
+    client: &'a Client,
+    portfolio: Portfolio,
+    transactions: Vec<Transaction>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    interval: Interval,
+    quote: String,
+    concurrency: usize,
+}
+
+impl<'a> GetPortfolioHistoryRequest<'a> {
+    pub fn new(
+        client: &'a Client,
+        portfolio: &Portfolio,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            client,
+            portfolio: portfolio.clone(),
+            transactions: vec![],
+            start,
+            end,
+            interval: Interval::Days1,
+            quote: String::from("usd"),
+            concurrency: 4,
+        }
+    }
+
+    /// Dated changes of the holdings, applied to the positions of the portfolio at their time
+    ///
+    /// Default: none
+    pub fn transactions(
+        &mut self,
+        transactions: Vec<Transaction>,
+    ) -> &'a mut GetPortfolioHistoryRequest<'_> {
+        self.transactions = transactions;
+        self
+    }
+
+    /// Spacing of the points, e.g. `1h` or `1d`
+    ///
+    /// Default: `1d`
+    pub fn interval(&mut self, interval: Interval) -> &'a mut GetPortfolioHistoryRequest<'_> {
+        self.interval = interval;
+        self
+    }
+
+    /// Quote of values (available values: `usd` `btc`)
+    ///
+    /// Default: `"usd"`
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetPortfolioHistoryRequest<'_> {
+        self.quote = quote.to_lowercase();
+        self
+    }
+
+    /// Maximum number of coins whose ticks are fetched at once, at least 1
+    ///
+    /// Default: `4`
+    pub fn concurrency(&mut self, concurrency: usize) -> &'a mut GetPortfolioHistoryRequest<'_> {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub async fn send(&self) -> Result<Vec<ValuePoint>, Error> {
+        validate::one_of("quote", &self.quote, &HISTORICAL_QUOTES)?;
+
+        if self.end < self.start {
+            return Err(Error::Validation {
+                field: "end",
+                reason: format!("must not be before start {}", self.start),
+            });
+        }
+
+        let mut coin_ids: Vec<&CoinId> = self
+            .portfolio
+            .positions()
+            .iter()
+            .map(|position| &position.coin_id)
+            .chain(self.transactions.iter().map(|t| &t.coin_id))
+            .collect();
+        coin_ids.sort();
+        coin_ids.dedup();
+
+        let prices: HashMap<CoinId, Samples> = stream::iter(coin_ids)
+            .map(|coin_id| async move {
+                let start = match self.first_data(coin_id).await? {
+                    Some(first) => self.start.max(first),
+                    None => self.start,
+                };
+
+                Ok::<_, Error>((coin_id.clone(), self.prices(coin_id, start).await?))
+            })
+            .buffered(self.concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let times = grid(self.start, self.end, self.interval.duration());
+
+        Ok(self
+            .portfolio
+            .sampled_history(&self.transactions, prices, &times))
+    }
+
+    /// `first_data_at` of the ticker of `coin_id`, `None` if the coin has no readable ticker
+    async fn first_data(&self, coin_id: &CoinId) -> Result<Option<DateTime<Utc>>, Error> {
+        let ticker = match self.client.ticker(coin_id).send().await {
+            Ok(ticker) => ticker,
+            Err(Error::InvalidParameter | Error::Decode(_)) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        Ok(DateTime::parse_from_rfc3339(&ticker.first_data_at)
+            .ok()
+            .map(|time| time.with_timezone(&Utc)))
+    }
+
+    /// Prices of `coin_id` from `start` to the end of the range, one cached window at a time.
+    /// Windows are aligned on the Unix epoch so that requests over overlapping ranges share them.
+    async fn prices(&self, coin_id: &CoinId, start: DateTime<Utc>) -> Result<Samples, Error> {
+        let span = self.interval.duration() * MAX_HISTORICAL_TICKS;
+        let mut prices = vec![];
+
+        for (from, to) in window::aligned(start, self.end, span) {
+            let key = (
+                coin_id.clone(),
+                self.quote.clone(),
+                self.interval,
+                from.timestamp(),
+                to.timestamp(),
+            );
+
+            prices.extend(PriceCache::samples(self.client, key, MAX_HISTORICAL_TICKS).await?);
+        }
+
+        Ok(prices)
+    }
+}
@@ -0,0 +1,343 @@
+use crate::client::Client;
+use crate::coins::CoinDetails;
+use crate::currency::QuoteCurrency;
+use crate::error::Error;
+use crate::id::{CoinId, TagId};
+use crate::number::{amount_from_f64, amount_to_f64, Amount, Price};
+use crate::tickers::{Ticker, TickerQuote};
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+
+mod cost_basis;
+pub use cost_basis::{
+    CostBasis, EntryKind, GetCostBasisRequest, LedgerEntry, Lot, LotMethod, RealizedGain,
+    UnrealizedGain, YearGains,
+};
+
+mod history;
+pub use history::{GetPortfolioHistoryRequest, Transaction, ValuePoint};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Amount of a coin held
+pub struct Position {
+    pub coin_id: CoinId,
+    pub amount: Amount,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Set of positions, at most one per coin
+pub struct Portfolio {
+    positions: Vec<Position>,
+}
+
+impl Portfolio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `amount` of `coin_id`, merging it into the existing position of that coin
+    pub fn add(&mut self, coin_id: impl Into<CoinId>, amount: impl Into<Amount>) -> &mut Portfolio {
+        let (coin_id, amount) = (coin_id.into(), amount.into());
+
+        match self.positions.iter_mut().find(|p| p.coin_id == coin_id) {
+            Some(position) => position.amount += amount,
+            None => self.positions.push(Position { coin_id, amount }),
+        }
+
+        self
+    }
+
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Values the positions with prices of `tickers`, in each of the `quotes` they were
+    /// returned with
+    pub fn value(&self, tickers: &[Ticker], quotes: &[QuoteCurrency]) -> Valuation {
+        let tickers: HashMap<&CoinId, &Ticker> =
+            tickers.iter().map(|ticker| (&ticker.id, ticker)).collect();
+
+        let mut missing = vec![];
+        let mut positions = vec![];
+
+        for position in &self.positions {
+            let ticker = match tickers.get(&position.coin_id) {
+                Some(ticker) => ticker,
+                None => {
+                    missing.push(position.coin_id.clone());
+                    continue;
+                }
+            };
+
+            let values = quotes
+                .iter()
+                .filter_map(|currency| {
+                    let quote = ticker.quotes.get(currency)?;
+                    Some((currency.clone(), PositionQuote::new(position.amount, quote)))
+                })
+                .collect();
+
+            positions.push(PositionValue {
+                coin_id: position.coin_id.clone(),
+                name: ticker.name.clone(),
+                symbol: ticker.symbol.clone(),
+                amount: position.amount,
+                values,
+            });
+        }
+
+        let mut totals: HashMap<QuoteCurrency, Totals> = HashMap::new();
+
+        for quote in positions.iter().flat_map(|position| &position.values) {
+            let total = totals.entry(quote.0.clone()).or_default();
+            total.value += quote.1.value;
+            total.pnl_24h += quote.1.pnl_24h;
+            total.pnl_7d += quote.1.pnl_7d;
+        }
+
+        for position in &mut positions {
+            for (currency, quote) in &mut position.values {
+                quote.weight = weight(quote.value, totals[currency].value);
+            }
+        }
+
+        Valuation {
+            quotes: quotes.to_vec(),
+            positions,
+            totals,
+            missing,
+            tags: vec![],
+            untagged: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Value of a position in one quote currency
+pub struct PositionQuote {
+    pub price: Price,
+    pub value: Amount,
+
+    /// Share of the portfolio value, between `0` and `1`
+    pub weight: f64,
+
+    /// Change of the position value over the last 24 hours, from `percent_change_24h`
+    pub pnl_24h: Amount,
+
+    /// Change of the position value over the last 7 days, from `percent_change_7d`
+    pub pnl_7d: Amount,
+}
+
+impl PositionQuote {
+    fn new(amount: Amount, quote: &TickerQuote) -> Self {
+        let value = amount * quote.price;
+
+        Self {
+            price: quote.price,
+            value,
+            weight: 0.0,
+            pnl_24h: pnl(value, quote.percent_change_24h),
+            pnl_7d: pnl(value, quote.percent_change_7d),
+        }
+    }
+}
+
+/// Gain of `value` since it was `percent_change` percent lower
+fn pnl(value: Amount, percent_change: f64) -> Amount {
+    let share = percent_change / (100.0 + percent_change);
+
+    amount_from_f64(share)
+        .map(|share| value * share)
+        .unwrap_or_default()
+}
+
+fn weight(value: Amount, total: Amount) -> f64 {
+    match total == Amount::default() {
+        true => 0.0,
+        false => amount_to_f64(value) / amount_to_f64(total),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Valued position
+pub struct PositionValue {
+    pub coin_id: CoinId,
+    pub name: String,
+    pub symbol: String,
+    pub amount: Amount,
+    pub values: HashMap<QuoteCurrency, PositionQuote>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Portfolio totals in one quote currency
+pub struct Totals {
+    pub value: Amount,
+    pub pnl_24h: Amount,
+    pub pnl_7d: Amount,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Value of the positions with a given tag in one quote currency
+pub struct TagAllocation {
+    pub tag_id: TagId,
+    pub name: String,
+    pub quote: QuoteCurrency,
+    pub value: Amount,
+
+    /// Share of the portfolio value. Coins usually have several tags, so weights of all tags add
+    /// up to more than `1`.
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Valuation of a [`Portfolio`]
+pub struct Valuation {
+    pub quotes: Vec<QuoteCurrency>,
+    pub positions: Vec<PositionValue>,
+    pub totals: HashMap<QuoteCurrency, Totals>,
+
+    /// Coins of the portfolio without a ticker, left out of the valuation
+    pub missing: Vec<CoinId>,
+
+    /// Allocation by tag, sorted by decreasing value. Empty unless requested with
+    /// [`GetPortfolioValuationRequest::tags`] or computed with
+    /// [`allocation_by_tag`](Valuation::allocation_by_tag).
+    pub tags: Vec<TagAllocation>,
+
+    /// Coins whose details could not be fetched for [`GetPortfolioValuationRequest::tags`], left
+    /// out of `tags`
+    pub untagged: Vec<CoinId>,
+}
+
+impl Valuation {
+    /// Value of the positions per tag of their coin in every quote currency, from the `tags` of
+    /// `details`
+    pub fn allocation_by_tag(&self, details: &[CoinDetails]) -> Vec<TagAllocation> {
+        let details: HashMap<&CoinId, &CoinDetails> =
+            details.iter().map(|coin| (&coin.id, coin)).collect();
+        let mut tags: HashMap<(TagId, QuoteCurrency), TagAllocation> = HashMap::new();
+
+        for position in &self.positions {
+            let coin_tags = match details.get(&position.coin_id) {
+                Some(coin) => &coin.tags,
+                None => continue,
+            };
+
+            for tag in coin_tags {
+                for (currency, quote) in &position.values {
+                    let allocation = tags
+                        .entry((tag.id.clone(), currency.clone()))
+                        .or_insert_with(|| TagAllocation {
+                            tag_id: tag.id.clone(),
+                            name: tag.name.clone(),
+                            quote: currency.clone(),
+                            value: Amount::default(),
+                            weight: 0.0,
+                        });
+
+                    allocation.value += quote.value;
+                    allocation.weight += quote.weight;
+                }
+            }
+        }
+
+        let mut tags: Vec<TagAllocation> = tags.into_values().collect();
+        tags.sort_by(|a, b| {
+            b.value
+                .partial_cmp(&a.value)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.tag_id.cmp(&b.tag_id))
+        });
+
+        tags
+    }
+}
+
+/// Request for valuing a [`Portfolio`] with a single
+/// [/tickers](https://api.coinpaprika.com/#tag/Tickers/operation/getTickers) call
+pub struct GetPortfolioValuationRequest<'a> {
+    client: &'a Client,
+    portfolio: Portfolio,
+    quotes: Vec<QuoteCurrency>,
+    tags: bool,
+    concurrency: usize,
+}
+
+impl<'a> GetPortfolioValuationRequest<'a> {
+    pub fn new(client: &'a Client, portfolio: &Portfolio) -> Self {
+        Self {
+            client,
+            portfolio: portfolio.clone(),
+            quotes: vec![QuoteCurrency::Usd],
+            tags: false,
+            concurrency: 4,
+        }
+    }
+
+    /// Quote currencies to value the portfolio in. Up to 3 quotes at once, see
+    /// [`QuoteCurrency`] for currently allowed values.
+    ///
+    /// Default: `USD`
+    pub fn quotes<Q: Into<QuoteCurrency>>(
+        &mut self,
+        quotes: Vec<Q>,
+    ) -> &'a mut GetPortfolioValuationRequest<'_> {
+        self.quotes = quotes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether to fill [`Valuation::tags`], at the cost of one
+    /// [/coins/{coin_id}](https://api.coinpaprika.com/#tag/Coins/operation/getCoinById) call per
+    /// position. Coins whose details fail to load are listed in [`Valuation::untagged`] instead
+    /// of failing the valuation.
+    ///
+    /// Default: `false`
+    pub fn tags(&mut self, tags: bool) -> &'a mut GetPortfolioValuationRequest<'_> {
+        self.tags = tags;
+        self
+    }
+
+    /// Maximum number of coin details requests in flight at once, at least 1
+    ///
+    /// Default: `4`
+    pub fn concurrency(&mut self, concurrency: usize) -> &'a mut GetPortfolioValuationRequest<'_> {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub async fn send(&self) -> Result<Valuation, Error> {
+        let tickers = self
+            .client
+            .tickers()
+            .quotes(self.quotes.clone())
+            .send()
+            .await?;
+
+        let mut valuation = self.portfolio.value(&tickers, &self.quotes);
+
+        if self.tags {
+            let results: Vec<(CoinId, Result<CoinDetails, Error>)> =
+                stream::iter(&valuation.positions)
+                    .map(|position| async move {
+                        let details = self.client.coin(&position.coin_id).send().await;
+                        (position.coin_id.clone(), details)
+                    })
+                    .buffered(self.concurrency.max(1))
+                    .collect()
+                    .await;
+
+            let mut details = vec![];
+
+            for (coin_id, result) in results {
+                match result {
+                    Ok(coin) => details.push(coin),
+                    Err(_) => valuation.untagged.push(coin_id),
+                }
+            }
+
+            valuation.tags = valuation.allocation_by_tag(&details);
+        }
+
+        Ok(valuation)
+    }
+}
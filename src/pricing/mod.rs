@@ -0,0 +1,396 @@
+use crate::client::Client;
+use crate::error::Error;
+use crate::id::CoinId;
+use crate::number::{amount_from_f64, Amount, Price};
+use crate::tickers::{ApiPlan, HistoricalTick, Interval};
+use crate::validate::{self, HISTORICAL_QUOTES};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+mod rates;
+pub use rates::{Currency, GetRateTableRequest, RateTable};
+
+/// Id of the US dollar, whose USD price is always `1` and never fetched
+pub static USD_ID: &str = "usd-us-dollars";
+
+/// Maximum number of windows kept by the price cache of a [`Client`]
+static MAX_CACHED_WINDOWS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How the price at a timestamp is derived from the samples around it
+pub enum PriceAtMode {
+    /// Price of the sample closest to the timestamp
+    Nearest,
+
+    /// Linear interpolation between the samples right before and right after the timestamp,
+    /// or the nearest sample when the timestamp is not between two samples
+    Interpolated,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Historical price of a coin at a timestamp
+pub struct PriceAt {
+    pub coin_id: CoinId,
+    pub quote: String,
+
+    /// Requested timestamp
+    pub at: DateTime<Utc>,
+
+    pub price: Price,
+
+    /// Timestamp of the sample closest to `at`
+    pub sample_time: DateTime<Utc>,
+
+    /// Distance between `sample_time` and `at`
+    pub distance: Duration,
+
+    /// Interval of the samples the price comes from
+    pub interval: Interval,
+
+    /// Whether `price` is interpolated between two samples
+    pub interpolated: bool,
+}
+
+/// Coin, quote, interval and bounds in seconds of a window of samples
+pub(crate) type WindowKey = (CoinId, String, Interval, i64, i64);
+pub(crate) type Samples = Vec<(DateTime<Utc>, Price)>;
+
+#[derive(Debug, Default)]
+/// Windows of samples fetched by [`GetPriceAtRequest`] and
+/// [`GetPortfolioHistoryRequest`](crate::portfolio::GetPortfolioHistoryRequest). Once
+/// [`MAX_CACHED_WINDOWS`] are cached, the oldest inserted windows are evicted first.
+pub(crate) struct PriceCache {
+    windows: Mutex<CachedWindows>,
+}
+
+#[derive(Debug, Default)]
+struct CachedWindows {
+    samples: HashMap<WindowKey, Samples>,
+
+    /// Keys of `samples` in insertion order
+    order: VecDeque<WindowKey>,
+}
+
+impl PriceCache {
+    /// Samples of the window of `key` from the cache of `client`, or from at most `limit`
+    /// historical ticks. A window is only cached once its end has passed, as it may still receive
+    /// samples until then.
+    pub(crate) async fn samples(
+        client: &Client,
+        key: WindowKey,
+        limit: i32,
+    ) -> Result<Samples, Error> {
+        if let Some(samples) = client.price_cache.get(&key) {
+            return Ok(samples);
+        }
+
+        let (coin_id, quote, interval, start, end) = &key;
+
+        let ticks: Vec<HistoricalTick> = client
+            .historical_ticks(coin_id)
+            .start(&start.to_string())
+            .end(&end.to_string())
+            .interval(*interval)
+            .limit(limit)
+            .quote(quote)
+            .send()
+            .await?;
+
+        let samples: Samples = ticks
+            .into_iter()
+            .filter_map(|tick| Some((tick.datetime()?, tick.price)))
+            .collect();
+
+        if *end < Utc::now().timestamp() {
+            client.price_cache.insert(key, samples.clone());
+        }
+
+        Ok(samples)
+    }
+
+    fn get(&self, key: &WindowKey) -> Option<Samples> {
+        self.windows.lock().ok()?.samples.get(key).cloned()
+    }
+
+    fn insert(&self, key: WindowKey, samples: Samples) {
+        if let Ok(mut windows) = self.windows.lock() {
+            if windows.samples.insert(key.clone(), samples).is_none() {
+                windows.order.push_back(key);
+            }
+
+            while windows.order.len() > MAX_CACHED_WINDOWS {
+                if let Some(oldest) = windows.order.pop_front() {
+                    windows.samples.remove(&oldest);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        if let Ok(mut windows) = self.windows.lock() {
+            windows.samples.clear();
+            windows.order.clear();
+        }
+    }
+}
+
+/// Request for the price of a coin at an arbitrary timestamp, from the samples of
+/// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
+/// around it.
+///
+/// Intervals are tried from the finest available in the plan up to `24h`, moving to a coarser one
+/// when the API refuses the interval ([`Error::InsufficientPlan`] or
+/// [`Error::InvalidRequestError`], e.g. for a range the plan does not cover) or has no sample
+/// around the timestamp. Each attempt fetches the two samples bracketing the timestamp. Samples
+/// are cached in the [`Client`], so repeated lookups close to each other cost a single request.
+pub struct GetPriceAtRequest<'a> {
+    client: &'a Client,
+    coin_id: CoinId,
+    at: DateTime<Utc>,
+    quote: String,
+    mode: PriceAtMode,
+    plan: Option<ApiPlan>,
+}
+
+impl<'a> GetPriceAtRequest<'a> {
+    pub fn new(
+        client: &'a Client,
+        coin_id: impl Into<CoinId>,
+        at: DateTime<Utc>,
+        quote: &str,
+    ) -> Self {
+        Self {
+            client,
+            coin_id: coin_id.into(),
+            at,
+            quote: quote.to_lowercase(),
+            mode: PriceAtMode::Nearest,
+            plan: None,
+        }
+    }
+
+    /// Default: [`PriceAtMode::Nearest`]
+    pub fn mode(&mut self, mode: PriceAtMode) -> &'a mut GetPriceAtRequest<'_> {
+        self.mode = mode;
+        self
+    }
+
+    /// API plan of the client, to skip intervals it does not allow
+    ///
+    /// Default: all intervals are tried
+    pub fn plan(&mut self, plan: ApiPlan) -> &'a mut GetPriceAtRequest<'_> {
+        self.plan = Some(plan);
+        self
+    }
+
+    /// `None` if no interval has samples around the timestamp, e.g. before the coin was listed.
+    /// Fails with the last refusal when the API refuses every interval.
+    pub async fn send(&self) -> Result<Option<PriceAt>, Error> {
+        validate::one_of("quote", &self.quote, &HISTORICAL_QUOTES)?;
+
+        let finest = self
+            .plan
+            .map_or(Interval::Minutes5, |plan| plan.finest_interval());
+        let intervals = Interval::ALL
+            .into_iter()
+            .filter(|interval| *interval >= finest && *interval <= Interval::Hours24);
+
+        let mut refused = None;
+        let mut answered = false;
+
+        for interval in intervals {
+            let samples = match self.samples(interval).await {
+                Ok(samples) => samples,
+                Err(error @ (Error::InsufficientPlan | Error::InvalidRequestError)) => {
+                    refused = Some(error);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            answered = true;
+
+            if let Some(price) = self.pick(&samples, interval) {
+                return Ok(Some(price));
+            }
+        }
+
+        // Every interval being refused points at the plan or the request, not at missing data.
+        match (answered, refused) {
+            (false, Some(error)) => Err(error),
+            _ => Ok(None),
+        }
+    }
+
+    /// Samples of the window of one `interval` step starting at or before `at`
+    async fn samples(&self, interval: Interval) -> Result<Samples, Error> {
+        let step = interval.duration().num_seconds();
+        let start = self.at.timestamp().div_euclid(step) * step;
+        let key = (
+            self.coin_id.clone(),
+            self.quote.clone(),
+            interval,
+            start,
+            start + step,
+        );
+
+        PriceCache::samples(self.client, key, 2).await
+    }
+
+    fn pick(&self, samples: &[(DateTime<Utc>, Price)], interval: Interval) -> Option<PriceAt> {
+        let before = samples.iter().rfind(|(time, _)| *time <= self.at);
+        let after = samples.iter().find(|(time, _)| *time >= self.at);
+
+        let nearest = match (before, after) {
+            (Some(before), Some(after)) if after.0 - self.at < self.at - before.0 => after,
+            (Some(before), _) => before,
+            (None, after) => after?,
+        };
+
+        let (price, interpolated) = match (self.mode, before, after) {
+            (PriceAtMode::Interpolated, Some(before), Some(after)) if before.0 < after.0 => {
+                let elapsed = (self.at - before.0).num_seconds() as f64;
+                let total = (after.0 - before.0).num_seconds() as f64;
+                let weight = amount_from_f64(elapsed / total)?;
+
+                (before.1 + (after.1 - before.1) * weight, true)
+            }
+            _ => (nearest.1, false),
+        };
+
+        Some(PriceAt {
+            coin_id: self.coin_id.clone(),
+            quote: self.quote.clone(),
+            at: self.at,
+            price,
+            sample_time: nearest.0,
+            distance: (nearest.0 - self.at).abs(),
+            interval,
+            interpolated,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Conversion between two currencies at a past timestamp
+pub struct ConversionAt {
+    pub base_id: CoinId,
+    pub quote_id: CoinId,
+    pub at: DateTime<Utc>,
+    pub amount: Amount,
+
+    /// `amount` of `base_id` expressed in `quote_id`
+    pub converted: Amount,
+
+    /// Price of one `base_id` in `quote_id`
+    pub rate: Price,
+
+    /// USD price of `base_id` used for the conversion, `None` for [`USD_ID`]
+    pub base_price: Option<PriceAt>,
+
+    /// USD price of `quote_id` used for the conversion, `None` for [`USD_ID`]
+    pub quote_price: Option<PriceAt>,
+}
+
+/// Request for converting an amount between two coins, or a coin and the US dollar ([`USD_ID`]), at
+/// a past timestamp, using the cross rate of their USD prices at that time as looked up by
+/// [`GetPriceAtRequest`].
+///
+/// Other ids are looked up like coins. The API does not document historical ticks for fiat
+/// currencies such as `eur-euro`, so converting them is not supported: an id the API has no
+/// historical ticks for fails with [`Error::InvalidParameter`].
+pub struct GetConversionAtRequest<'a> {
+    client: &'a Client,
+    base_id: CoinId,
+    quote_id: CoinId,
+    amount: Amount,
+    at: DateTime<Utc>,
+    mode: PriceAtMode,
+    plan: Option<ApiPlan>,
+}
+
+impl<'a> GetConversionAtRequest<'a> {
+    pub fn new(
+        client: &'a Client,
+        base_id: impl Into<CoinId>,
+        quote_id: impl Into<CoinId>,
+        amount: impl Into<Amount>,
+        at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            client,
+            base_id: base_id.into(),
+            quote_id: quote_id.into(),
+            amount: amount.into(),
+            at,
+            mode: PriceAtMode::Nearest,
+            plan: None,
+        }
+    }
+
+    /// Default: [`PriceAtMode::Nearest`]
+    pub fn mode(&mut self, mode: PriceAtMode) -> &'a mut GetConversionAtRequest<'_> {
+        self.mode = mode;
+        self
+    }
+
+    /// API plan of the client, to skip intervals it does not allow
+    ///
+    /// Default: all intervals are tried
+    pub fn plan(&mut self, plan: ApiPlan) -> &'a mut GetConversionAtRequest<'_> {
+        self.plan = Some(plan);
+        self
+    }
+
+    /// `None` if the USD price of either currency is unknown or zero at that time
+    pub async fn send(&self) -> Result<Option<ConversionAt>, Error> {
+        let base_price = self.usd_price(&self.base_id).await?;
+        let quote_price = self.usd_price(&self.quote_id).await?;
+
+        let (base_usd, quote_usd) = match (&base_price, &quote_price) {
+            (Some(base), Some(quote)) => (usd_value(base), usd_value(quote)),
+            _ => return Ok(None),
+        };
+
+        if quote_usd == Price::default() {
+            return Ok(None);
+        }
+
+        let rate = base_usd / quote_usd;
+
+        Ok(Some(ConversionAt {
+            base_id: self.base_id.clone(),
+            quote_id: self.quote_id.clone(),
+            at: self.at,
+            amount: self.amount,
+            converted: self.amount * rate,
+            rate,
+            base_price: base_price.flatten(),
+            quote_price: quote_price.flatten(),
+        }))
+    }
+
+    /// `Some(None)` for [`USD_ID`], `None` if the price is unknown
+    async fn usd_price(&self, coin_id: &CoinId) -> Result<Option<Option<PriceAt>>, Error> {
+        if coin_id.as_str() == USD_ID {
+            return Ok(Some(None));
+        }
+
+        let mut request = self.client.price_at(coin_id, self.at, "usd");
+
+        let price = match self.plan {
+            Some(plan) => request.mode(self.mode).plan(plan).send().await?,
+            None => request.mode(self.mode).send().await?,
+        };
+
+        Ok(price.map(Some))
+    }
+}
+
+/// USD price of a lookup, `1` for [`USD_ID`]
+fn usd_value(price: &Option<PriceAt>) -> Price {
+    match price {
+        Some(price) => price.price,
+        None => Price::from(1u8),
+    }
+}
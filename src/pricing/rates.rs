@@ -0,0 +1,210 @@
+use crate::client::Client;
+use crate::currency::QuoteCurrency;
+use crate::error::Error;
+use crate::id::CoinId;
+use crate::number::{Amount, Price};
+use crate::tickers::Ticker;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// Coin used to derive the rates of quote currencies, when present in the tickers
+static REFERENCE_ID: &str = "btc-bitcoin";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Currency a [`RateTable`] converts from or to
+pub enum Currency {
+    Coin(CoinId),
+    Quote(QuoteCurrency),
+}
+
+impl From<CoinId> for Currency {
+    fn from(coin_id: CoinId) -> Self {
+        Currency::Coin(coin_id)
+    }
+}
+
+impl From<&CoinId> for Currency {
+    fn from(coin_id: &CoinId) -> Self {
+        Currency::Coin(coin_id.clone())
+    }
+}
+
+impl From<QuoteCurrency> for Currency {
+    fn from(quote: QuoteCurrency) -> Self {
+        Currency::Quote(quote)
+    }
+}
+
+/// Coin ids always contain a dash (e.g. `btc-bitcoin`), so values without one are read as quote
+/// currencies (e.g. `usd` or `EUR`).
+impl From<&str> for Currency {
+    fn from(value: &str) -> Self {
+        match value.contains('-') {
+            true => Currency::Coin(CoinId::from(value)),
+            false => Currency::Quote(QuoteCurrency::from(value.to_uppercase())),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Snapshot of USD prices of all active coins and of quote currencies, converting between any
+/// two of them without further requests.
+///
+/// Built from a single [/tickers](https://api.coinpaprika.com/#tag/Tickers/operation/getTickers)
+/// response requested in USD plus up to two other quote currencies, whose rates are derived from
+/// the quotes of `btc-bitcoin` (or of the ticker with the most quotes).
+pub struct RateTable {
+    quotes: Vec<QuoteCurrency>,
+    coins: HashMap<CoinId, Price>,
+    currencies: HashMap<QuoteCurrency, Price>,
+    last_updated: HashMap<CoinId, DateTime<Utc>>,
+    fetched_at: DateTime<Utc>,
+}
+
+impl RateTable {
+    /// Builds a table from tickers returned with a `USD` quote. Tickers without it are ignored.
+    pub fn from_tickers(tickers: &[Ticker]) -> Self {
+        let usd = QuoteCurrency::Usd;
+        let mut coins = HashMap::new();
+        let mut last_updated = HashMap::new();
+
+        for ticker in tickers {
+            if let Some(quote) = ticker.quotes.get(&usd) {
+                coins.insert(ticker.id.clone(), quote.price);
+            }
+
+            if let Ok(time) = DateTime::parse_from_rfc3339(&ticker.last_updated) {
+                last_updated.insert(ticker.id.clone(), time.with_timezone(&Utc));
+            }
+        }
+
+        let reference = tickers
+            .iter()
+            .find(|ticker| ticker.id.as_str() == REFERENCE_ID)
+            .or_else(|| tickers.iter().max_by_key(|ticker| ticker.quotes.len()));
+
+        let mut currencies = HashMap::from([(usd.clone(), Price::from(1u8))]);
+
+        if let Some(reference) = reference {
+            if let Some(usd_quote) = reference.quotes.get(&usd) {
+                for (currency, quote) in &reference.quotes {
+                    if quote.price != Price::default() {
+                        currencies.insert(currency.clone(), usd_quote.price / quote.price);
+                    }
+                }
+            }
+        }
+
+        let mut quotes: Vec<QuoteCurrency> = currencies.keys().cloned().collect();
+        quotes.retain(|quote| *quote != usd);
+
+        Self {
+            quotes,
+            coins,
+            currencies,
+            last_updated,
+            fetched_at: Utc::now(),
+        }
+    }
+
+    /// USD price of one unit of `currency`
+    pub fn usd_price(&self, currency: impl Into<Currency>) -> Option<Price> {
+        match currency.into() {
+            Currency::Coin(coin_id) => self.coins.get(&coin_id).copied(),
+            Currency::Quote(quote) => self.currencies.get(&quote).copied(),
+        }
+    }
+
+    /// Price of one unit of `from` in `to`. `None` if either is unknown or `to` is worth nothing.
+    pub fn rate(&self, from: impl Into<Currency>, to: impl Into<Currency>) -> Option<Price> {
+        let from = self.usd_price(from)?;
+        let to = self.usd_price(to).filter(|to| *to != Price::default())?;
+
+        Some(from / to)
+    }
+
+    /// `amount` of `from` expressed in `to`
+    pub fn convert(
+        &self,
+        amount: impl Into<Amount>,
+        from: impl Into<Currency>,
+        to: impl Into<Currency>,
+    ) -> Option<Amount> {
+        Some(amount.into() * self.rate(from, to)?)
+    }
+
+    /// When the tickers were fetched or the table was built
+    pub fn fetched_at(&self) -> DateTime<Utc> {
+        self.fetched_at
+    }
+
+    /// `last_updated` of the ticker of `coin_id`
+    pub fn last_updated(&self, coin_id: impl Into<CoinId>) -> Option<DateTime<Utc>> {
+        self.last_updated.get(&coin_id.into()).copied()
+    }
+
+    /// How old the price of `coin_id` is at `now`
+    pub fn staleness(&self, coin_id: impl Into<CoinId>, now: DateTime<Utc>) -> Option<Duration> {
+        Some(now - self.last_updated(coin_id)?)
+    }
+
+    /// Coins whose price was last updated more than `max_age` before `now`
+    pub fn stale(&self, max_age: Duration, now: DateTime<Utc>) -> Vec<&CoinId> {
+        self.last_updated
+            .iter()
+            .filter(|(_, updated)| now - **updated > max_age)
+            .map(|(coin_id, _)| coin_id)
+            .collect()
+    }
+
+    /// Replaces the rates with a new snapshot requested with the same quote currencies
+    pub async fn refresh(&mut self, client: &Client) -> Result<(), Error> {
+        *self = client
+            .rate_table()
+            .quotes(self.quotes.clone())
+            .send()
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Request for a [`RateTable`] built from
+/// [/tickers](https://api.coinpaprika.com/#tag/Tickers/operation/getTickers)
+pub struct GetRateTableRequest<'a> {
+    client: &'a Client,
+    quotes: Vec<QuoteCurrency>,
+}
+
+impl<'a> GetRateTableRequest<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            quotes: vec![],
+        }
+    }
+
+    /// Quote currencies to include besides `USD`, up to 2. `BTC` and `ETH` are also available as
+    /// coins without being listed.
+    pub fn quotes<Q: Into<QuoteCurrency>>(
+        &mut self,
+        quotes: Vec<Q>,
+    ) -> &'a mut GetRateTableRequest<'_> {
+        self.quotes = quotes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub async fn send(&self) -> Result<RateTable, Error> {
+        let mut quotes = vec![QuoteCurrency::Usd];
+        quotes.extend(
+            self.quotes
+                .iter()
+                .filter(|q| **q != QuoteCurrency::Usd)
+                .cloned(),
+        );
+
+        let tickers = self.client.tickers().quotes(quotes).send().await?;
+
+        Ok(RateTable::from_tickers(&tickers))
+    }
+}
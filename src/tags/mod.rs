@@ -1,13 +1,28 @@
 use crate::client::{Client, Response};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
 use crate::error::Error;
+use crate::id::{CoinId, TagId};
+use crate::validate;
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
+/// Additional fields accepted by tags requests
+static ADDITIONAL_FIELDS: [&str; 2] = ["coins", "icos"];
+
+string_enum! {
+    /// Type of a tag
+    pub enum TagType {
+        Functional => "functional",
+        Technical => "technical",
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 /// Basic information about cryptocurrency tag
 pub struct Tag {
     /// ID of the tag
-    pub id: String,
+    pub id: TagId,
 
     /// Name of the tag
     pub name: String,
@@ -23,13 +38,17 @@ pub struct Tag {
 
     #[serde(rename = "type")]
     /// Type of tag
-    pub tag_type: String,
+    pub tag_type: TagType,
 
     /// Coins associated with the tag
-    pub coins: Option<Vec<String>>,
+    pub coins: Option<Vec<CoinId>>,
 
     /// ICOs associated with the tag
     pub icos: Option<Vec<String>>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Request for getting basic information about cryptocurrencies tags (categories):
@@ -49,12 +68,21 @@ impl<'a> GetTagsRequest<'a> {
 
     /// Array of additional fields to include in query result for each tag. Currently supported
     /// values are: `"coins"` and `"icos"`.
-    pub fn additional_fields(&mut self, additional_fields: Vec<&str>) -> &'a mut GetTagsRequest {
+    pub fn additional_fields(
+        &mut self,
+        additional_fields: Vec<&str>,
+    ) -> &'a mut GetTagsRequest<'_> {
         self.additional_fields = additional_fields.iter().map(|&q| String::from(q)).collect();
         self
     }
 
     pub async fn send(&self) -> Result<Vec<Tag>, Error> {
+        validate::all_of(
+            "additional_fields",
+            &self.additional_fields,
+            &ADDITIONAL_FIELDS,
+        )?;
+
         let query = match self.additional_fields.len() {
             0 => vec![],
             _ => vec![("additional_fields", self.additional_fields.join(","))],
@@ -68,7 +96,7 @@ impl<'a> GetTagsRequest<'a> {
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<Tag> = response.response.json().await?;
+        let data: Vec<Tag> = response.json().await?;
 
         Ok(data)
     }
@@ -78,27 +106,33 @@ impl<'a> GetTagsRequest<'a> {
 /// [/tags/{tag_id}](https://api.coinpaprika.com/#tag/Tags/paths/~1tags~1%7Btag_id%7D/get)
 pub struct GetTagRequest<'a> {
     client: &'a Client,
-    tag_id: String,
+    tag_id: TagId,
     additional_fields: Vec<String>,
 }
 
 impl<'a> GetTagRequest<'a> {
-    pub fn new(client: &'a Client, tag_id: &str) -> Self {
+    pub fn new(client: &'a Client, tag_id: impl Into<TagId>) -> Self {
         Self {
             client,
-            tag_id: String::from(tag_id),
+            tag_id: tag_id.into(),
             additional_fields: vec![],
         }
     }
 
     /// Array of additional fields to include in query result for each tag. Currently supported
     /// values are: `"coins"` and `"icos"`.
-    pub fn additional_fields(&mut self, additional_fields: Vec<&str>) -> &'a mut GetTagRequest {
+    pub fn additional_fields(&mut self, additional_fields: Vec<&str>) -> &'a mut GetTagRequest<'_> {
         self.additional_fields = additional_fields.iter().map(|&q| String::from(q)).collect();
         self
     }
 
     pub async fn send(&self) -> Result<Tag, Error> {
+        validate::all_of(
+            "additional_fields",
+            &self.additional_fields,
+            &ADDITIONAL_FIELDS,
+        )?;
+
         let query = match self.additional_fields.len() {
             0 => vec![],
             _ => vec![("additional_fields", self.additional_fields.join(","))],
@@ -107,12 +141,16 @@ impl<'a> GetTagRequest<'a> {
         let request: RequestBuilder = self
             .client
             .client
-            .get(format!("{}/tags/{}", self.client.api_url, self.tag_id))
+            .get(format!(
+                "{}/tags/{}",
+                self.client.api_url,
+                self.tag_id.encoded()
+            ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Tag = response.response.json().await?;
+        let data: Tag = response.json().await?;
 
         Ok(data)
     }
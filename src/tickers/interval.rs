@@ -0,0 +1,208 @@
+use crate::error::Error;
+use crate::tickers::MAX_HISTORICAL_TICKS;
+use chrono::{DateTime, Duration, Utc};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Interval between points returned by historical ticks requests
+pub enum Interval {
+    Minutes5,
+    Minutes10,
+    Minutes15,
+    Minutes30,
+    Minutes45,
+    Hours1,
+    Hours2,
+    Hours3,
+    Hours6,
+    Hours12,
+    Hours24,
+    Days1,
+    Days7,
+    Days14,
+    Days30,
+    Days90,
+    Days365,
+}
+
+impl Interval {
+    /// All intervals, from the finest to the coarsest
+    pub const ALL: [Interval; 17] = [
+        Interval::Minutes5,
+        Interval::Minutes10,
+        Interval::Minutes15,
+        Interval::Minutes30,
+        Interval::Minutes45,
+        Interval::Hours1,
+        Interval::Hours2,
+        Interval::Hours3,
+        Interval::Hours6,
+        Interval::Hours12,
+        Interval::Hours24,
+        Interval::Days1,
+        Interval::Days7,
+        Interval::Days14,
+        Interval::Days30,
+        Interval::Days90,
+        Interval::Days365,
+    ];
+
+    /// Returns the value as used by the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::Minutes5 => "5m",
+            Interval::Minutes10 => "10m",
+            Interval::Minutes15 => "15m",
+            Interval::Minutes30 => "30m",
+            Interval::Minutes45 => "45m",
+            Interval::Hours1 => "1h",
+            Interval::Hours2 => "2h",
+            Interval::Hours3 => "3h",
+            Interval::Hours6 => "6h",
+            Interval::Hours12 => "12h",
+            Interval::Hours24 => "24h",
+            Interval::Days1 => "1d",
+            Interval::Days7 => "7d",
+            Interval::Days14 => "14d",
+            Interval::Days30 => "30d",
+            Interval::Days90 => "90d",
+            Interval::Days365 => "365d",
+        }
+    }
+
+    /// Time between two consecutive points
+    pub fn duration(&self) -> Duration {
+        match self {
+            Interval::Minutes5 => Duration::minutes(5),
+            Interval::Minutes10 => Duration::minutes(10),
+            Interval::Minutes15 => Duration::minutes(15),
+            Interval::Minutes30 => Duration::minutes(30),
+            Interval::Minutes45 => Duration::minutes(45),
+            Interval::Hours1 => Duration::hours(1),
+            Interval::Hours2 => Duration::hours(2),
+            Interval::Hours3 => Duration::hours(3),
+            Interval::Hours6 => Duration::hours(6),
+            Interval::Hours12 => Duration::hours(12),
+            Interval::Hours24 | Interval::Days1 => Duration::days(1),
+            Interval::Days7 => Duration::days(7),
+            Interval::Days14 => Duration::days(14),
+            Interval::Days30 => Duration::days(30),
+            Interval::Days90 => Duration::days(90),
+            Interval::Days365 => Duration::days(365),
+        }
+    }
+}
+
+impl FromStr for Interval {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Interval::ALL
+            .iter()
+            .find(|interval| interval.as_str() == value)
+            .copied()
+            .ok_or_else(|| Error::Validation {
+                field: "interval",
+                reason: format!("unknown interval `{}`", value),
+            })
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// coinpaprika.com API plan, which limits the available historical ticks
+pub enum ApiPlan {
+    Free,
+    Starter,
+    Pro,
+    Business,
+    Enterprise,
+}
+
+impl ApiPlan {
+    /// The finest interval available in the plan
+    pub fn finest_interval(&self) -> Interval {
+        match self {
+            ApiPlan::Free => Interval::Hours24,
+            ApiPlan::Starter => Interval::Hours1,
+            ApiPlan::Pro | ApiPlan::Business | ApiPlan::Enterprise => Interval::Minutes5,
+        }
+    }
+
+    /// How far back from now historical ticks are available. `None` if there is no limit.
+    pub fn history(&self) -> Option<Duration> {
+        match self {
+            ApiPlan::Free => Some(Duration::days(365)),
+            ApiPlan::Starter => Some(Duration::days(5 * 365)),
+            ApiPlan::Pro | ApiPlan::Business | ApiPlan::Enterprise => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Expected shape of a historical ticks request for a given time range and interval
+pub struct HistoricalTicksPlan {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub interval: Interval,
+
+    /// Number of points in the range, assuming points aligned to multiples of the interval since
+    /// the Unix epoch
+    pub points: i64,
+
+    /// `limit` to request, capped at [`MAX_HISTORICAL_TICKS`]
+    pub limit: i32,
+
+    /// Number of requests needed to fetch the whole range
+    pub requests: i64,
+}
+
+impl HistoricalTicksPlan {
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>, interval: Interval) -> Self {
+        let step = interval.duration().num_seconds();
+        let (first, last) = (start.timestamp(), end.timestamp());
+
+        let points = match first <= last {
+            true => last.div_euclid(step) - (first + step - 1).div_euclid(step) + 1,
+            false => 0,
+        };
+        let max = i64::from(MAX_HISTORICAL_TICKS);
+
+        Self {
+            start,
+            end,
+            interval,
+            points,
+            limit: points.clamp(1, max) as i32,
+            requests: (points + max - 1) / max,
+        }
+    }
+
+    /// Returns the finest interval plan that fits in a single request and is available in `plan`.
+    pub fn finest(start: DateTime<Utc>, end: DateTime<Utc>, plan: ApiPlan) -> Option<Self> {
+        Interval::ALL
+            .iter()
+            .map(|&interval| Self::new(start, end, interval))
+            .find(|candidate| candidate.fits_single_request() && candidate.is_available(plan))
+    }
+
+    /// Whether the whole range can be fetched with one request
+    pub fn fits_single_request(&self) -> bool {
+        self.requests <= 1
+    }
+
+    /// Whether `plan` allows both the interval and the start of the range
+    pub fn is_available(&self, plan: ApiPlan) -> bool {
+        let history = match plan.history() {
+            Some(history) => self.start >= Utc::now() - history,
+            None => true,
+        };
+
+        self.interval >= plan.finest_interval() && history
+    }
+}
@@ -1,24 +1,86 @@
 use crate::client::{Client, Response};
+use crate::currency::{quotes_query, QuoteCurrency};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
+use crate::decode::{lenient_list, LenientList};
 use crate::error::Error;
+use crate::id::CoinId;
+use crate::number::{deserialize_option_amount, Amount, Price};
+use crate::validate::{self, HISTORICAL_QUOTES};
 use chrono::prelude::*;
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use std::collections::HashMap;
+
+mod interval;
+pub use interval::{ApiPlan, HistoricalTicksPlan, Interval};
+
+mod range;
+pub use range::GetHistoricalTicksRangeRequest;
+
+/// Maximum number of rows returned by a single historical ticks request
+pub static MAX_HISTORICAL_TICKS: i32 = 5000;
 
 #[derive(Debug, Serialize, Deserialize)]
 /// Price data of a single cryptocurrency on coinpaprika.com
 pub struct Ticker {
-    pub id: String,
+    pub id: CoinId,
     pub name: String,
     pub symbol: String,
     pub rank: isize,
-    pub circulating_supply: i64,
-    pub total_supply: i64,
-    pub max_supply: i64,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub circulating_supply: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub total_supply: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub max_supply: Option<Amount>,
+
     pub beta_value: f64,
     pub first_data_at: String,
     pub last_updated: String,
-    pub quotes: Value,
+    pub quotes: HashMap<QuoteCurrency, TickerQuote>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+/// Price data of a cryptocurrency in a single quote currency
+pub struct TickerQuote {
+    pub price: Price,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub volume_24h: Option<Amount>,
+
+    pub volume_24h_change_24h: f64,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub market_cap: Option<Amount>,
+
+    pub market_cap_change_24h: f64,
+    pub percent_change_15m: f64,
+    pub percent_change_30m: f64,
+    pub percent_change_1h: f64,
+    pub percent_change_6h: f64,
+    pub percent_change_12h: f64,
+    pub percent_change_24h: f64,
+    pub percent_change_7d: f64,
+    pub percent_change_30d: f64,
+    pub percent_change_1y: f64,
+    pub ath_price: Option<Price>,
+
+    /// RFC3999 (ISO-8601) format
+    pub ath_date: Option<String>,
+
+    pub percent_from_price_ath: Option<f64>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,16 +88,33 @@ pub struct Ticker {
 pub struct HistoricalTick {
     /// RFC3999 (ISO-8601) format
     pub timestamp: String,
-    pub price: f64,
-    pub volume_24h: i64,
-    pub market_cap: i64,
+    pub price: Price,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub volume_24h: Option<Amount>,
+
+    #[serde(default, deserialize_with = "deserialize_option_amount")]
+    pub market_cap: Option<Amount>,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl HistoricalTick {
+    /// Parsed `timestamp`, `None` if it is not a valid RFC3339 date
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+    }
 }
 
 /// Request for getting data of all active cryptocurrencies on coinpaprika.com
 /// [/tickers](https://api.coinpaprika.com/#tag/Tickers/operation/getTickers)
 pub struct GetTickersRequest<'a> {
     client: &'a Client,
-    quotes: Vec<String>,
+    quotes: Vec<QuoteCurrency>,
 }
 
 impl<'a> GetTickersRequest<'a> {
@@ -46,32 +125,40 @@ impl<'a> GetTickersRequest<'a> {
         }
     }
 
-    /// List of quotes to return. Up to 3 quotes at once. Currently allowed values:
-    /// BTC, ETH, USD, EUR, PLN, KRW, GBP, CAD, JPY, RUB, TRY, NZD, AUD, CHF, UAH, HKD, SGD, NGN,
-    /// PHP, MXN, BRL, THB, CLP, CNY, CZK, DKK, HUF, IDR, ILS, INR, MYR, NOK, PKR, SEK, TWD, ZAR,
-    /// VND, BOB, COP, PEN, ARS, ISK
-    pub fn quotes(&mut self, quotes: Vec<&str>) -> &'a mut GetTickersRequest {
-        self.quotes = quotes.iter().map(|&q| String::from(q)).collect();
+    /// List of quotes to return. Up to 3 quotes at once, see [`QuoteCurrency`] for currently
+    /// allowed values.
+    pub fn quotes<Q: Into<QuoteCurrency>>(
+        &mut self,
+        quotes: Vec<Q>,
+    ) -> &'a mut GetTickersRequest<'_> {
+        self.quotes = quotes.into_iter().map(Into::into).collect();
         self
     }
 
     pub async fn send(&self) -> Result<Vec<Ticker>, Error> {
-        let query = match self.quotes.len() {
-            0 => vec![],
-            _ => vec![("quotes", self.quotes.join(","))],
-        };
+        let response: Response = self.client.request(self.request()?).await?;
 
-        let request: RequestBuilder = self
-            .client
-            .client
-            .get(format!("{}/tickers", self.client.api_url))
-            .query(&query);
+        let data: Vec<Ticker> = response.json().await?;
 
-        let response: Response = self.client.request(request).await?;
+        Ok(data)
+    }
 
-        let data: Vec<Ticker> = response.response.json().await?;
+    /// Like [`send`](Self::send), but decodes the response item by item. Items that fail to decode
+    /// are skipped and reported in [`LenientList::diagnostics`].
+    pub async fn send_lenient(&self) -> Result<LenientList<Ticker>, Error> {
+        let response: Response = self.client.request(self.request()?).await?;
 
-        Ok(data)
+        lenient_list(response).await
+    }
+
+    fn request(&self) -> Result<RequestBuilder, Error> {
+        let query = quotes_query(&self.quotes)?;
+
+        Ok(self
+            .client
+            .client
+            .get(format!("{}/tickers", self.client.api_url))
+            .query(&query))
     }
 }
 
@@ -79,67 +166,64 @@ impl<'a> GetTickersRequest<'a> {
 /// [/ticker/{coin_id}](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersById)
 pub struct GetTickerRequest<'a> {
     client: &'a Client,
-    coin_id: String,
-    quotes: Vec<String>,
+    coin_id: CoinId,
+    quotes: Vec<QuoteCurrency>,
 }
 
 impl<'a> GetTickerRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
         Self {
             client,
-            coin_id: String::from(coin_id),
+            coin_id: coin_id.into(),
             quotes: vec![],
         }
     }
 
-    /// List of quotes to return. Up to 3 quotes at once. Currently allowed values:
-    /// BTC, ETH, USD, EUR, PLN, KRW, GBP, CAD, JPY, RUB, TRY, NZD, AUD, CHF, UAH, HKD, SGD, NGN,
-    /// PHP, MXN, BRL, THB, CLP, CNY, CZK, DKK, HUF, IDR, ILS, INR, MYR, NOK, PKR, SEK, TWD, ZAR,
-    /// VND, BOB, COP, PEN, ARS, ISK
-    pub fn quotes(&mut self, quotes: Vec<&str>) -> &'a mut GetTickerRequest {
-        self.quotes = quotes.iter().map(|&q| String::from(q)).collect();
+    /// List of quotes to return. Up to 3 quotes at once, see [`QuoteCurrency`] for currently
+    /// allowed values.
+    pub fn quotes<Q: Into<QuoteCurrency>>(
+        &mut self,
+        quotes: Vec<Q>,
+    ) -> &'a mut GetTickerRequest<'_> {
+        self.quotes = quotes.into_iter().map(Into::into).collect();
         self
     }
 
     pub async fn send(&self) -> Result<Ticker, Error> {
-        let query = match self.quotes.len() {
-            0 => vec![],
-            _ => vec![("quotes", self.quotes.join(","))],
-        };
+        let query = quotes_query(&self.quotes)?;
 
         let request: RequestBuilder = self
             .client
             .client
-            .get(format!("{}/tickers/{}", self.client.api_url, self.coin_id))
+            .get(format!(
+                "{}/tickers/{}",
+                self.client.api_url,
+                self.coin_id.encoded()
+            ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Ticker = response.response.json().await?;
+        let data: Ticker = response.json().await?;
 
         Ok(data)
     }
 }
 
-/// Request for getting historical data for a given cryptocurrency on coinpaprika.com
-/// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
-pub struct GetHistoricalTicksRequest<'a> {
-    client: &'a Client,
-    coin_id: String,
-    start: String,
-    end: Option<String>,
-    limit: Option<String>,
-    quote: Option<String>,
-    interval: Option<String>,
+/// Query parameters shared by historical ticks requests
+pub(crate) struct HistoricalTicksQuery {
+    pub(crate) start: String,
+    pub(crate) end: Option<String>,
+    pub(crate) limit: Option<i32>,
+    pub(crate) quote: Option<String>,
+    pub(crate) interval: Option<Interval>,
 }
 
-impl<'a> GetHistoricalTicksRequest<'a> {
-    pub fn new(client: &'a Client, coin_id: &str) -> Self {
+impl HistoricalTicksQuery {
+    pub(crate) fn new() -> Self {
         let now: DateTime<Utc> = Utc::now(); // e.g. `2014-11-28T12:45:59.324310806Z`
 
         Self {
-            client,
-            coin_id: String::from(coin_id),
             start: format!("{}-{}-{}", now.year(), now.month(), now.day()),
             end: None,
             limit: None,
@@ -148,14 +232,63 @@ impl<'a> GetHistoricalTicksRequest<'a> {
         }
     }
 
+    /// Validates the parameters and builds the query string pairs.
+    pub(crate) fn to_query(&self) -> Result<Vec<(&'static str, String)>, Error> {
+        if let Some(limit) = self.limit {
+            validate::range("limit", limit, 1, MAX_HISTORICAL_TICKS)?;
+        }
+
+        if let Some(quote) = &self.quote {
+            validate::one_of("quote", quote, &HISTORICAL_QUOTES)?;
+        }
+
+        let mut query: Vec<(&str, String)> = vec![("start", self.start.clone())];
+
+        if let Some(end) = &self.end {
+            query.push(("end", end.clone()));
+        }
+
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+
+        if let Some(quote) = &self.quote {
+            query.push(("quote", quote.clone()));
+        }
+
+        if let Some(interval) = self.interval {
+            query.push(("interval", interval.as_str().to_string()));
+        }
+
+        Ok(query)
+    }
+}
+
+/// Request for getting historical data for a given cryptocurrency on coinpaprika.com
+/// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
+pub struct GetHistoricalTicksRequest<'a> {
+    client: &'a Client,
+    coin_id: CoinId,
+    query: HistoricalTicksQuery,
+}
+
+impl<'a> GetHistoricalTicksRequest<'a> {
+    pub fn new(client: &'a Client, coin_id: impl Into<CoinId>) -> Self {
+        Self {
+            client,
+            coin_id: coin_id.into(),
+            query: HistoricalTicksQuery::new(),
+        }
+    }
+
     /// Start point for historical data
     ///
     /// Supported formats:
     /// * RFC3999 (ISO-8601) eg. 2018-02-15T05:15:00Z
     /// * Simple date (yyyy-mm-dd) eg. 2018-02-15
     /// * Unix timestamp (in seconds) eg. 1518671700
-    pub fn start(&mut self, start: &str) -> &'a mut GetHistoricalTicksRequest {
-        self.start = String::from(start);
+    pub fn start(&mut self, start: &str) -> &'a mut GetHistoricalTicksRequest<'_> {
+        self.query.start = String::from(start);
         self
     }
 
@@ -167,67 +300,52 @@ impl<'a> GetHistoricalTicksRequest<'a> {
     /// RFC3999 (ISO-8601) eg. 2018-02-15T05:15:00Z
     /// Simple date (yyyy-mm-dd) eg. 2018-02-15
     /// Unix timestamp (in seconds) eg. 1518671700
-    pub fn end(&mut self, end: &str) -> &'a mut GetHistoricalTicksRequest {
-        self.end = Some(String::from(end));
+    pub fn end(&mut self, end: &str) -> &'a mut GetHistoricalTicksRequest<'_> {
+        self.query.end = Some(String::from(end));
         self
     }
 
     /// Limit of result rows (max `5000`)
     ///
     /// Default: `1000`
-    pub fn limit(&mut self, limit: i32) -> &'a mut GetHistoricalTicksRequest {
-        self.limit = Some(limit.to_string());
+    pub fn limit(&mut self, limit: i32) -> &'a mut GetHistoricalTicksRequest<'_> {
+        self.query.limit = Some(limit);
         self
     }
 
     /// Returned data quote (available values: `usd` `btc`)
     ///
     /// Default: `"usd"`
-    pub fn quote(&mut self, quote: &str) -> &'a mut GetHistoricalTicksRequest {
-        self.quote = Some(String::from(quote));
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetHistoricalTicksRequest<'_> {
+        self.query.quote = Some(String::from(quote));
         self
     }
 
-    /// Returned points interval (available values: `5m` `10m` `15m` `30m` `45m` `1h` `2h` `3h`
-    /// `6h` `12h` `24h` `1d` `7d` `14d` `30d` `90d` `365d`)
+    /// Returned points interval. Use [`HistoricalTicksPlan`] to pick an interval for a given
+    /// time range.
     ///
-    /// Default: `"5m"`
-    pub fn interval(&mut self, interval: &str) -> &'a mut GetHistoricalTicksRequest {
-        self.interval = Some(String::from(interval));
+    /// Default: [`Interval::Minutes5`]
+    pub fn interval(&mut self, interval: Interval) -> &'a mut GetHistoricalTicksRequest<'_> {
+        self.query.interval = Some(interval);
         self
     }
 
     pub async fn send(&self) -> Result<Vec<HistoricalTick>, Error> {
-        let mut query: Vec<(&str, &str)> = vec![("start", self.start.as_ref())];
-
-        if let Some(end) = &self.end {
-            query.push(("end", end));
-        }
-
-        if let Some(limit) = &self.limit {
-            query.push(("limit", limit));
-        }
-
-        if let Some(quote) = &self.quote {
-            query.push(("quote", quote));
-        }
-
-        if let Some(interval) = &self.interval {
-            query.push(("interval", interval));
-        }
+        let query = self.query.to_query()?;
 
         let request: RequestBuilder = self
             .client
             .client
             .get(format!(
                 "{}/tickers/{}/historical",
-                self.client.api_url, self.coin_id
+                self.client.api_url,
+                self.coin_id.encoded()
             ))
             .query(&query);
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Vec<HistoricalTick> = response.response.json().await?;
+        let data: Vec<HistoricalTick> = response.json().await?;
 
         Ok(data)
     }
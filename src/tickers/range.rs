@@ -0,0 +1,152 @@
+use crate::client::Client;
+use crate::error::Error;
+use crate::id::CoinId;
+use crate::tickers::{HistoricalTick, Interval, MAX_HISTORICAL_TICKS};
+use crate::window;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use std::future;
+
+/// Request for getting historical ticks over an arbitrary time range, split into as many
+/// [/ticker/{coin_id}/historical](https://api.coinpaprika.com/#tag/Tickers/operation/getTickersHistoricalById)
+/// requests as needed to stay within [`MAX_HISTORICAL_TICKS`] rows each
+pub struct GetHistoricalTicksRangeRequest<'a> {
+    client: &'a Client,
+    coin_id: CoinId,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    interval: Interval,
+    quote: String,
+    concurrency: usize,
+    after: Option<DateTime<Utc>>,
+}
+
+impl<'a> GetHistoricalTicksRangeRequest<'a> {
+    pub fn new(
+        client: &'a Client,
+        coin_id: impl Into<CoinId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            client,
+            coin_id: coin_id.into(),
+            start,
+            end,
+            interval: Interval::Minutes5,
+            quote: String::from("usd"),
+            concurrency: 1,
+            after: None,
+        }
+    }
+
+    /// Returned points interval. Each window holds at most [`MAX_HISTORICAL_TICKS`] points of it.
+    ///
+    /// Default: [`Interval::Minutes5`]
+    pub fn interval(&mut self, interval: Interval) -> &'a mut GetHistoricalTicksRangeRequest<'_> {
+        self.interval = interval;
+        self
+    }
+
+    /// Returned data quote (available values: `usd` `btc`)
+    ///
+    /// Default: `"usd"`
+    pub fn quote(&mut self, quote: &str) -> &'a mut GetHistoricalTicksRangeRequest<'_> {
+        self.quote = String::from(quote);
+        self
+    }
+
+    /// Maximum number of window requests in flight at once, at least 1. Ticks are still yielded
+    /// in order.
+    ///
+    /// Default: `1`
+    pub fn concurrency(
+        &mut self,
+        concurrency: usize,
+    ) -> &'a mut GetHistoricalTicksRangeRequest<'_> {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Continue an interrupted backfill: starts the range at `last`, the last tick received
+    /// before the failure, and skips it and any earlier tick.
+    pub fn resume_after(
+        &mut self,
+        last: &HistoricalTick,
+    ) -> &'a mut GetHistoricalTicksRangeRequest<'_> {
+        if let Some(time) = last.datetime() {
+            self.start = time;
+            self.after = Some(time);
+        }
+        self
+    }
+
+    /// Windows of at most [`MAX_HISTORICAL_TICKS`] points covering the range, in order. Both
+    /// bounds of a window are inclusive.
+    pub fn windows(&self) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        window::split(
+            self.start,
+            self.end,
+            self.interval.duration() * MAX_HISTORICAL_TICKS,
+        )
+    }
+
+    /// Stream of ticks in ascending order, without duplicates at window boundaries.
+    ///
+    /// The stream ends after the first error; use [`resume_after`](Self::resume_after) with the
+    /// last received tick to continue from there.
+    pub fn stream(&self) -> impl Stream<Item = Result<HistoricalTick, Error>> + 'a {
+        let client = self.client;
+        let coin_id = self.coin_id.clone();
+        let interval = self.interval;
+        let quote = self.quote.clone();
+        let mut last = self.after;
+
+        stream::iter(self.windows())
+            .map(move |(start, end)| {
+                let coin_id = coin_id.clone();
+                let quote = quote.clone();
+
+                async move {
+                    client
+                        .historical_ticks(coin_id)
+                        .start(&start.timestamp().to_string())
+                        .end(&end.timestamp().to_string())
+                        .limit(MAX_HISTORICAL_TICKS)
+                        .quote(&quote)
+                        .interval(interval)
+                        .send()
+                        .await
+                }
+            })
+            .buffered(self.concurrency.max(1))
+            .scan(false, |failed, result| {
+                let item = match *failed {
+                    true => None,
+                    false => Some(result),
+                };
+                *failed = matches!(item, Some(Err(_)));
+                future::ready(item)
+            })
+            .map_ok(move |ticks| {
+                let ticks: Vec<HistoricalTick> = ticks
+                    .into_iter()
+                    .filter(|tick| match (tick.datetime(), last) {
+                        (Some(time), Some(last)) => time > last,
+                        _ => true,
+                    })
+                    .collect();
+
+                if let Some(time) = ticks.iter().rev().find_map(HistoricalTick::datetime) {
+                    last = Some(time);
+                }
+
+                stream::iter(ticks.into_iter().map(Ok))
+            })
+            .try_flatten()
+    }
+
+    pub async fn send(&self) -> Result<Vec<HistoricalTick>, Error> {
+        self.stream().try_collect().await
+    }
+}
@@ -1,20 +1,38 @@
 use crate::client::{Client, Response};
+#[cfg(feature = "extra-fields")]
+use crate::decode::Extra;
 use crate::error::Error;
+use crate::id::CoinId;
+use crate::number::{Amount, Price};
+use crate::validate;
 use reqwest_middleware::RequestBuilder;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// Maximum number of search results per category
+pub static MAX_SEARCH_RESULTS: i32 = 250;
+
+/// Categories accepted by search requests
+static SEARCH_CATEGORIES: [&str; 5] = ["currencies", "exchanges", "icos", "people", "tags"];
+
+/// Modifiers accepted by search requests
+static SEARCH_MODIFIERS: [&str; 1] = ["symbol_search"];
+
 #[derive(Debug, Serialize, Deserialize)]
 /// Price data of a single cryptocurrency on coinpaprika.com
 pub struct PriceConversion {
-    pub base_currency_id: String,
+    pub base_currency_id: CoinId,
     pub base_currency_name: String,
     pub base_price_last_updated: String,
-    pub quote_currency_id: String,
+    pub quote_currency_id: CoinId,
     pub quote_currency_name: String,
     pub quote_price_last_updated: String,
-    pub amount: i32,
-    pub price: f64,
+    pub amount: Amount,
+    pub price: Price,
+
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 /// Request for getting currencies, exchanges, icos, people, tags on coinpaprika.com for a given
@@ -25,7 +43,7 @@ pub struct GetSearchRequest<'a> {
     q: String,
     c: Option<Vec<String>>,
     modifier: Option<String>,
-    limit: Option<String>,
+    limit: Option<i32>,
 }
 
 impl<'a> GetSearchRequest<'a> {
@@ -44,14 +62,14 @@ impl<'a> GetSearchRequest<'a> {
     ///
     /// Default: `["currencies", "exchanges", "icos", "people", "tags"]` (all categories are
     /// returned)
-    pub fn c(&mut self, categories: Vec<&str>) -> &'a mut GetSearchRequest {
+    pub fn c(&mut self, categories: Vec<&str>) -> &'a mut GetSearchRequest<'_> {
         self.c = Some(categories.iter().map(|&q| String::from(q)).collect());
         self
     }
 
     /// Set modifier for search results. Available options: `symbol_search` - search only by symbol
     /// (works for currencies only)
-    pub fn modifier(&mut self, modifier: &str) -> &'a mut GetSearchRequest {
+    pub fn modifier(&mut self, modifier: &str) -> &'a mut GetSearchRequest<'_> {
         self.modifier = Some(String::from(modifier));
         self
     }
@@ -59,12 +77,24 @@ impl<'a> GetSearchRequest<'a> {
     /// Limit of results per category (max `250`)
     ///
     /// Default: `6`
-    pub fn limit(&mut self, limit: i32) -> &'a mut GetSearchRequest {
-        self.limit = Some(limit.to_string());
+    pub fn limit(&mut self, limit: i32) -> &'a mut GetSearchRequest<'_> {
+        self.limit = Some(limit);
         self
     }
 
     pub async fn send(&self) -> Result<Value, Error> {
+        if let Some(c) = &self.c {
+            validate::all_of("c", c, &SEARCH_CATEGORIES)?;
+        }
+
+        if let Some(modifier) = &self.modifier {
+            validate::one_of("modifier", modifier, &SEARCH_MODIFIERS)?;
+        }
+
+        if let Some(limit) = self.limit {
+            validate::range("limit", limit, 1, MAX_SEARCH_RESULTS)?;
+        }
+
         let mut query: Vec<(String, String)> = vec![("q".to_string(), self.q.to_string())];
 
         if let Some(c) = &self.c {
@@ -87,7 +117,7 @@ impl<'a> GetSearchRequest<'a> {
 
         let response: Response = self.client.request(request).await?;
 
-        let data: Value = response.response.json().await?;
+        let data: Value = response.json().await?;
 
         Ok(data)
     }
@@ -97,31 +127,37 @@ impl<'a> GetSearchRequest<'a> {
 /// [/price-converter](https://api.coinpaprika.com/#tag/Tools/paths/~1price-converter/get)
 pub struct GetPriceConversionRequest<'a> {
     client: &'a Client,
-    base_currency_id: String,
-    quote_currency_id: String,
+    base_currency_id: CoinId,
+    quote_currency_id: CoinId,
     amount: String,
 }
 
 impl<'a> GetPriceConversionRequest<'a> {
-    pub fn new(client: &'a Client, base_currency_id: &str, quote_currency_id: &str) -> Self {
+    pub fn new(
+        client: &'a Client,
+        base_currency_id: impl Into<CoinId>,
+        quote_currency_id: impl Into<CoinId>,
+    ) -> Self {
         Self {
             client,
-            base_currency_id: String::from(base_currency_id),
-            quote_currency_id: String::from(quote_currency_id),
+            base_currency_id: base_currency_id.into(),
+            quote_currency_id: quote_currency_id.into(),
             amount: String::from("0"),
         }
     }
 
+    /// Amount of base currency to convert
+    ///
     /// Default: 0
-    pub fn amount(&mut self, amount: i32) -> &'a mut GetPriceConversionRequest {
-        self.amount = amount.to_string();
+    pub fn amount(&mut self, amount: impl Into<Amount>) -> &'a mut GetPriceConversionRequest<'_> {
+        self.amount = amount.into().to_string();
         self
     }
 
     pub async fn send(&self) -> Result<PriceConversion, Error> {
         let query: Vec<(&str, &str)> = vec![
-            ("base_currency_id", self.base_currency_id.as_ref()),
-            ("quote_currency_id", self.quote_currency_id.as_ref()),
+            ("base_currency_id", self.base_currency_id.as_str()),
+            ("quote_currency_id", self.quote_currency_id.as_str()),
             ("amount", self.amount.as_ref()),
         ];
 
@@ -133,7 +169,7 @@ impl<'a> GetPriceConversionRequest<'a> {
 
         let response: Response = self.client.request(request).await?;
 
-        let data: PriceConversion = response.response.json().await?;
+        let data: PriceConversion = response.json().await?;
 
         Ok(data)
     }
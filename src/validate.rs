@@ -0,0 +1,54 @@
+use crate::error::Error;
+
+/// Quotes accepted by historical endpoints
+pub(crate) static HISTORICAL_QUOTES: [&str; 2] = ["usd", "btc"];
+
+/// Checks that `value` of the `field` parameter lies within `min..=max`.
+pub(crate) fn range(field: &'static str, value: i32, min: i32, max: i32) -> Result<(), Error> {
+    if value < min || value > max {
+        return Err(Error::Validation {
+            field,
+            reason: format!("must be between {} and {}, got {}", min, max, value),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that `value` of the `field` parameter is at least `min`.
+pub(crate) fn at_least(field: &'static str, value: i32, min: i32) -> Result<(), Error> {
+    if value < min {
+        return Err(Error::Validation {
+            field,
+            reason: format!("must be at least {}, got {}", min, value),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that `value` of the `field` parameter is one of `allowed`, ignoring ASCII case.
+pub(crate) fn one_of(field: &'static str, value: &str, allowed: &[&str]) -> Result<(), Error> {
+    if !allowed
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(value))
+    {
+        return Err(Error::Validation {
+            field,
+            reason: format!("must be one of `{}`, got `{}`", allowed.join("`, `"), value),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that every value of the `field` parameter is one of `allowed`, ignoring ASCII case.
+pub(crate) fn all_of<S: AsRef<str>>(
+    field: &'static str,
+    values: &[S],
+    allowed: &[&str],
+) -> Result<(), Error> {
+    values
+        .iter()
+        .try_for_each(|value| one_of(field, value.as_ref(), allowed))
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Splits `start..=end` into consecutive windows of at most `span`, in order. Both bounds of a
+/// window are inclusive and a window starts one second after the end of the previous one.
+pub(crate) fn split(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    span: Duration,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut windows = vec![];
+    let mut from = start;
+
+    while from <= end {
+        let to = (from + span - Duration::seconds(1)).min(end);
+        windows.push((from, to));
+        from = to + Duration::seconds(1);
+    }
+
+    windows
+}
+
+/// Splits `start..=end` at multiples of `span` since the Unix epoch, in order. Both bounds of a
+/// window are inclusive and whole seconds. Unlike with [`split`], the windows inside the range do
+/// not depend on `start`, so overlapping ranges share them, e.g. as cache keys.
+pub(crate) fn aligned(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    span: Duration,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let span = span.num_seconds();
+    let mut windows = vec![];
+    let mut from = DateTime::from_timestamp(start.timestamp(), 0).unwrap_or(start);
+
+    while from <= end {
+        let next = (from.timestamp().div_euclid(span) + 1) * span;
+        let to = match DateTime::from_timestamp(next - 1, 0) {
+            Some(to) => to.min(end),
+            None => end,
+        };
+        windows.push((from, to));
+        from = to + Duration::seconds(1);
+    }
+
+    windows
+}